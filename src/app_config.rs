@@ -3,6 +3,7 @@ use std::fs;
 use std::env;
 use dirs::home_dir;
 use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
 use config::{Config, ConfigError, File};
 use ring::{aead, rand};
 use ring::rand::SecureRandom;
@@ -49,28 +50,53 @@ pub fn load_config() -> Result<Config, AppError> {
         .map_err(AppError::Config)?;
 
     // 检查必要的配置项
-    if config.get_string("jira_url").is_err() || config.get_string("username").is_err() || config.get_string("encrypted_password").is_err() {
+    if config.get_string("jira_url").is_err() || config.get_string("username").is_err() {
         return Err(AppError::ConfigMissing);
     }
 
-    // 处理加密密码
-    let encrypted_password = config.get_string("encrypted_password").map_err(AppError::Config)?;
-    let decrypted_password = decrypt_password(&encrypted_password).map_err(AppError::Config)?;
-    
-    // 创建新的 builder，包含原始配置和解密后的密码
-    let config = Config::builder()
-        .add_source(config.clone())
-        .set_override("password", decrypted_password) // 这里设置解密后的密码
-        .map_err(AppError::Config)?
-        .build()
-        .map_err(AppError::Config)?;
-
     Ok(config)
 }
 
+/// Returns true when a password is available, either encrypted in the config
+/// file or in plain text via `JIRA_GIT_PASSWORD`.
+pub fn has_password(config: &Config) -> bool {
+    config.get_string("encrypted_password").is_ok() || config.get_string("password").is_ok()
+}
+
+/// Resolves the JIRA password, decrypting `encrypted_password` when present.
+pub fn get_password(config: &Config) -> Result<String, AppError> {
+    match config.get_string("encrypted_password") {
+        Ok(encrypted) => decrypt_password(&encrypted).map_err(AppError::Config),
+        Err(_) => config.get_string("password").map_err(|_| AppError::ConfigMissing),
+    }
+}
+
+/// Reads an optional table from the configuration, falling back to its
+/// defaults when the table is absent.
+pub fn get_section<T: DeserializeOwned + Default>(config: &Config, key: &str) -> Result<T, AppError> {
+    match config.get::<T>(key) {
+        Ok(section) => Ok(section),
+        Err(ConfigError::NotFound(_)) => Ok(T::default()),
+        Err(e) => Err(AppError::Config(e)),
+    }
+}
+
 pub fn save_config(config: &AppConfig) -> Result<(), AppError> {
     let config_path = get_config_path();
-    let config_str = toml::to_string_pretty(config)
+
+    // 保留配置文件中手动添加的其他配置段（如 [commit_msg]）
+    let mut table = match fs::read_to_string(&config_path) {
+        Ok(existing) => existing.parse::<toml::Table>().unwrap_or_default(),
+        Err(_) => toml::Table::new(),
+    };
+    let credentials = toml::Table::try_from(config)
+        .map_err(|e| AppError::Config(ConfigError::Message(format!("Failed to serialize config: {}", e))))?;
+    if config.encrypted_password.is_none() {
+        table.remove("encrypted_password");
+    }
+    table.extend(credentials);
+
+    let config_str = toml::to_string_pretty(&table)
         .map_err(|e| AppError::Config(ConfigError::Message(format!("Failed to serialize config: {}", e))))?;
     fs::write(config_path, config_str)
         .map_err(|e| AppError::Config(ConfigError::Message(format!("Failed to write config file: {}", e))))
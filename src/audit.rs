@@ -0,0 +1,283 @@
+use crate::git::CommitInfo;
+use crate::issue::Issue;
+use crate::issue_key::{KeyExtractionSettings, KeyExtractor};
+use crate::message::{display_width, truncate_at_word};
+use crate::AppError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Width of the subject column of the table.
+const TABLE_SUBJECT_WIDTH: usize = 40;
+
+/// The `[audit]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AuditSettings {
+    /// Projects commits may refer to. Falls back to `key_extraction.projects`,
+    /// then `jira_id_prefix`; any project is accepted when all are empty.
+    pub projects: Vec<String>,
+    /// Fix version the issues should have when `--fix-version` is not given.
+    pub fix_version: Option<String>,
+    /// Do not report references to closed issues.
+    pub allow_closed: bool,
+    /// Regular expressions for subjects of commits that need no key, e.g. `^Bump version`.
+    pub ignore_subjects: Vec<String>,
+}
+
+impl AuditSettings {
+    /// Applies the fallbacks of `projects` when it is not set.
+    pub fn fill_projects(&mut self, key_projects: &[String], jira_id_prefix: Option<&str>) {
+        if self.projects.is_empty() {
+            self.projects = key_projects.to_vec();
+        }
+        if self.projects.is_empty() {
+            self.projects.extend(jira_id_prefix.filter(|p| !p.is_empty()).map(str::to_string));
+        }
+    }
+}
+
+/// Output formats of `audit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditFormat {
+    Table,
+    Json,
+    Junit,
+}
+
+impl AuditFormat {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value {
+            "table" => Ok(AuditFormat::Table),
+            "json" => Ok(AuditFormat::Json),
+            "junit" => Ok(AuditFormat::Junit),
+            other => Err(AppError::Other(format!("Unknown format '{}', expected table, json or junit", other))),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    MissingKey,
+    UnexpectedProject,
+    UnknownIssue,
+    ClosedIssue,
+    OtherFixVersion,
+}
+
+impl ProblemKind {
+    fn label(&self) -> &'static str {
+        match self {
+            ProblemKind::MissingKey => "missing key",
+            ProblemKind::UnexpectedProject => "unexpected project",
+            ProblemKind::UnknownIssue => "unknown issue",
+            ProblemKind::ClosedIssue => "closed issue",
+            ProblemKind::OtherFixVersion => "other fix version",
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub kind: ProblemKind,
+    pub key: Option<String>,
+    pub detail: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CommitAudit {
+    pub hash: String,
+    pub short_hash: String,
+    pub subject: String,
+    pub author: String,
+    pub problems: Vec<Problem>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AuditReport {
+    pub range: String,
+    pub commits: Vec<CommitAudit>,
+}
+
+/// Extracts the keys of every commit message. Keys of any project are kept,
+/// so references to other projects are reported as unexpected rather than
+/// missing.
+pub fn commit_keys(commits: &[CommitInfo], key_settings: &KeyExtractionSettings) -> Result<Vec<Vec<String>>, AppError> {
+    let extractor = KeyExtractor::new(&KeyExtractionSettings {
+        projects: Vec::new(),
+        ..key_settings.clone()
+    })?;
+    Ok(commits.iter().map(|c| extractor.extract_from_text(&c.message)).collect())
+}
+
+/// Checks the keys of every commit against the issues fetched for them.
+/// `keys` holds the keys of each commit, in the same order as `commits`.
+pub fn audit(
+    range: &str,
+    commits: &[CommitInfo],
+    keys: &[Vec<String>],
+    issues: &HashMap<String, Issue>,
+    fix_version: Option<&str>,
+    settings: &AuditSettings,
+) -> Result<AuditReport, AppError> {
+    let ignore = settings
+        .ignore_subjects
+        .iter()
+        .map(|pattern| Regex::new(pattern).map_err(|e| AppError::ConfigString(format!("Invalid audit.ignore_subjects pattern: {}", e))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let commits = commits
+        .iter()
+        .zip(keys)
+        .map(|(commit, keys)| {
+            let mut problems = Vec::new();
+            if keys.is_empty() && !ignore.iter().any(|re| re.is_match(&commit.subject)) {
+                problems.push(Problem {
+                    kind: ProblemKind::MissingKey,
+                    key: None,
+                    detail: "No JIRA key in the commit message".to_string(),
+                });
+            }
+            for key in keys {
+                problems.extend(check_key(key, issues.get(key), fix_version, settings));
+            }
+            CommitAudit {
+                hash: commit.hash.clone(),
+                short_hash: commit.short_hash.clone(),
+                subject: commit.subject.clone(),
+                author: commit.author.clone(),
+                problems,
+            }
+        })
+        .collect();
+
+    Ok(AuditReport {
+        range: range.to_string(),
+        commits,
+    })
+}
+
+fn check_key(key: &str, issue: Option<&Issue>, fix_version: Option<&str>, settings: &AuditSettings) -> Option<Problem> {
+    let problem = |kind, detail: String| {
+        Some(Problem {
+            kind,
+            key: Some(key.to_string()),
+            detail,
+        })
+    };
+
+    let project = key.rsplit_once('-').map_or(key, |(project, _)| project);
+    if !settings.projects.is_empty() && !settings.projects.iter().any(|p| p.eq_ignore_ascii_case(project)) {
+        return problem(ProblemKind::UnexpectedProject, format!("{} is not in {}", project, settings.projects.join(", ")));
+    }
+    let Some(issue) = issue else {
+        return problem(ProblemKind::UnknownIssue, format!("{} does not exist or is not visible", key));
+    };
+    let closed = issue.status.as_ref().and_then(|s| s.category.as_ref()).is_some_and(|c| c.key == "done");
+    if closed && !settings.allow_closed {
+        return problem(ProblemKind::ClosedIssue, format!("{} is {}", key, issue.status_name()));
+    }
+    match fix_version {
+        Some(version) if !issue.fix_versions.is_empty() && !issue.fix_versions.iter().any(|v| v == version) => problem(
+            ProblemKind::OtherFixVersion,
+            format!("{} is planned for {}", key, issue.fix_versions.join(", ")),
+        ),
+        _ => None,
+    }
+}
+
+impl AuditReport {
+    pub fn failed(&self) -> impl Iterator<Item = &CommitAudit> {
+        self.commits.iter().filter(|c| !c.problems.is_empty())
+    }
+
+    pub fn render(&self, format: AuditFormat) -> String {
+        match format {
+            AuditFormat::Table => self.render_table(),
+            AuditFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            AuditFormat::Junit => self.render_junit(),
+        }
+    }
+
+    fn render_table(&self) -> String {
+        let failed: Vec<&CommitAudit> = self.failed().collect();
+        if failed.is_empty() {
+            return format!("All {} commits in {} passed the audit\n", self.commits.len(), self.range);
+        }
+
+        let headers = ["COMMIT", "SUBJECT", "PROBLEM", "DETAIL"].map(str::to_string);
+        let rows: Vec<[String; 4]> = failed
+            .iter()
+            .flat_map(|c| {
+                c.problems.iter().map(|p| {
+                    [
+                        c.short_hash.clone(),
+                        truncate_at_word(&c.subject, TABLE_SUBJECT_WIDTH, "…"),
+                        p.kind.label().to_string(),
+                        p.detail.clone(),
+                    ]
+                })
+            })
+            .collect();
+        let mut widths = headers.clone().map(|h| display_width(&h));
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate().take(3) {
+                widths[i] = widths[i].max(display_width(cell));
+            }
+        }
+        let format_row = |cells: &[String; 4]| {
+            let mut line = String::new();
+            for (i, cell) in cells.iter().enumerate().take(3) {
+                line.push_str(cell);
+                line.push_str(&" ".repeat(widths[i] - display_width(cell) + 2));
+            }
+            line.push_str(&cells[3]);
+            line
+        };
+
+        let mut lines = vec![format_row(&headers)];
+        lines.extend(rows.iter().map(format_row));
+        lines.push(String::new());
+        lines.push(format!("{} of {} commits in {} have problems", failed.len(), self.commits.len(), self.range));
+        lines.join("\n") + "\n"
+    }
+
+    /// One test case per commit, so CI systems show every failing commit.
+    fn render_junit(&self) -> String {
+        let failures = self.failed().count();
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<testsuites>\n  <testsuite name=\"jira-audit {}\" tests=\"{}\" failures=\"{}\">\n",
+            escape_xml(&self.range),
+            self.commits.len(),
+            failures
+        ));
+        for commit in &self.commits {
+            let name = escape_xml(&format!("{} {}", commit.short_hash, commit.subject));
+            if commit.problems.is_empty() {
+                out.push_str(&format!("    <testcase classname=\"audit\" name=\"{}\"/>\n", name));
+                continue;
+            }
+            out.push_str(&format!("    <testcase classname=\"audit\" name=\"{}\">\n", name));
+            for problem in &commit.problems {
+                out.push_str(&format!(
+                    "      <failure type=\"{}\" message=\"{}\"/>\n",
+                    problem.kind.label(),
+                    escape_xml(&problem.detail)
+                ));
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n</testsuites>\n");
+        out
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,79 @@
+use crate::issue::Issue;
+use crate::jira::Transition;
+use crate::message::fill_template;
+use crate::slug::{sanitize_ref_name, slugify};
+use serde::Deserialize;
+
+/// Maps a JIRA issue type to the `{type}` part of branch names.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TypePrefix {
+    pub issue_type: String,
+    pub prefix: String,
+}
+
+/// The `[start]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct StartSettings {
+    /// Supports `{type}`, `{key}`, `{project}`, `{number}` and `{slug}`.
+    pub branch_pattern: String,
+    /// Branch to start from; the current HEAD when not set.
+    pub base_branch: Option<String>,
+    /// Fetch the base branch from `remote` first and start from the remote branch.
+    pub fetch: bool,
+    pub remote: String,
+    pub assign: bool,
+    /// Status to move the issue to; no transition when not set.
+    pub in_progress_status: Option<String>,
+    pub type_prefixes: Vec<TypePrefix>,
+    pub default_prefix: String,
+    pub max_slug_length: usize,
+}
+
+impl Default for StartSettings {
+    fn default() -> Self {
+        Self {
+            branch_pattern: "{type}/{key}-{slug}".to_string(),
+            base_branch: None,
+            fetch: false,
+            remote: "origin".to_string(),
+            assign: false,
+            in_progress_status: Some("In Progress".to_string()),
+            type_prefixes: vec![TypePrefix {
+                issue_type: "Bug".to_string(),
+                prefix: "bugfix".to_string(),
+            }],
+            default_prefix: "feature".to_string(),
+            max_slug_length: 50,
+        }
+    }
+}
+
+/// Builds the branch name for the issue from `branch_pattern`.
+pub fn branch_name(issue: &Issue, settings: &StartSettings) -> String {
+    let issue_type = issue.issue_type.as_deref().unwrap_or_default();
+    let prefix = settings
+        .type_prefixes
+        .iter()
+        .find(|p| p.issue_type.eq_ignore_ascii_case(issue_type))
+        .map_or(settings.default_prefix.as_str(), |p| p.prefix.as_str());
+    let (project, number) = issue.key.split_once('-').unwrap_or((&issue.key, ""));
+
+    let slug = slugify(&issue.summary, settings.max_slug_length);
+    let name = fill_template(
+        &settings.branch_pattern,
+        &[("type", prefix), ("key", &issue.key), ("project", project), ("number", number), ("slug", &slug)],
+    );
+
+    // 标题或类型为空时去掉残留的分隔符
+    sanitize_ref_name(name.trim_matches(['-', '_', '/']))
+}
+
+/// Finds the transition leading to the status, matching the target status
+/// name or the transition name, case-insensitively.
+pub fn find_transition<'a>(transitions: &'a [Transition], status: &str) -> Option<&'a Transition> {
+    transitions
+        .iter()
+        .find(|t| t.to.name.eq_ignore_ascii_case(status))
+        .or_else(|| transitions.iter().find(|t| t.name.eq_ignore_ascii_case(status)))
+}
@@ -0,0 +1,299 @@
+use crate::issue::Issue;
+use crate::jira::browse_url;
+use crate::message::fill_template;
+use crate::AppError;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Sections of a Keep a Changelog release, in the order they are written.
+/// Sections from the configuration that are not listed here come last.
+pub const SECTIONS: &[&str] = &["Added", "Changed", "Deprecated", "Removed", "Fixed", "Security"];
+
+/// Written when the changelog file does not exist yet.
+pub const CHANGELOG_HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+## [Unreleased]
+";
+
+/// Puts issues with one of the types or labels into a section.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SectionMapping {
+    pub section: String,
+    #[serde(default)]
+    pub issue_types: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl SectionMapping {
+    fn matches(&self, issue: &Issue) -> bool {
+        let type_matches = issue
+            .issue_type
+            .as_ref()
+            .is_some_and(|t| self.issue_types.iter().any(|m| m.eq_ignore_ascii_case(t)));
+        let label_matches = issue
+            .labels
+            .iter()
+            .any(|label| self.labels.iter().any(|m| m.eq_ignore_ascii_case(label)));
+        type_matches || label_matches
+    }
+}
+
+/// The `[changelog]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ChangelogSettings {
+    pub file: PathBuf,
+    /// Tag of a released version, e.g. `v{version}`.
+    pub tag_format: String,
+    /// Checked in order; the first matching mapping wins.
+    pub sections: Vec<SectionMapping>,
+    /// Section for issues no mapping matches.
+    pub default_section: String,
+    /// Supports `{key}`, `{summary}`, `{url}` and `{type}`.
+    pub entry: String,
+}
+
+impl Default for ChangelogSettings {
+    fn default() -> Self {
+        let mapping = |section: &str, issue_types: &[&str], labels: &[&str]| SectionMapping {
+            section: section.to_string(),
+            issue_types: issue_types.iter().map(|s| s.to_string()).collect(),
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+        };
+        Self {
+            file: PathBuf::from("CHANGELOG.md"),
+            tag_format: "v{version}".to_string(),
+            sections: vec![
+                mapping("Removed", &[], &["removed"]),
+                mapping("Deprecated", &[], &["deprecated"]),
+                mapping("Security", &[], &["security"]),
+                mapping("Fixed", &["Bug"], &[]),
+                mapping("Added", &["Story", "New Feature", "Epic"], &[]),
+            ],
+            default_section: "Changed".to_string(),
+            entry: "{summary} ([{key}]({url}))".to_string(),
+        }
+    }
+}
+
+impl ChangelogSettings {
+    pub fn tag(&self, version: &str) -> String {
+        self.tag_format.replace("{version}", version)
+    }
+}
+
+/// A generated changelog line for an issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub section: String,
+    pub key: String,
+    pub text: String,
+}
+
+/// One entry per issue, in the section its type or labels map to.
+pub fn entries(issues: &[Issue], settings: &ChangelogSettings, jira_url: &str) -> Vec<Entry> {
+    issues
+        .iter()
+        .map(|issue| {
+            let section = settings
+                .sections
+                .iter()
+                .find(|m| m.matches(issue))
+                .map_or(settings.default_section.as_str(), |m| m.section.as_str());
+            let text = fill_template(
+                &settings.entry,
+                &[
+                    ("key", &issue.key),
+                    ("summary", issue.summary.trim()),
+                    ("url", &browse_url(jira_url, &issue.key)),
+                    ("type", issue.issue_type.as_deref().unwrap_or_default()),
+                ],
+            );
+            Entry {
+                section: section.to_string(),
+                key: issue.key.clone(),
+                text,
+            }
+        })
+        .collect()
+}
+
+/// The latest released version, i.e. the first version heading that is not
+/// `Unreleased`.
+pub fn last_version(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(heading_version)
+        .find(|v| !v.eq_ignore_ascii_case("unreleased"))
+        .map(str::to_string)
+}
+
+/// Result of [`update`].
+#[derive(Debug, Clone)]
+pub struct Updated {
+    /// The whole new file.
+    pub content: String,
+    /// Only the new version section.
+    pub section: String,
+}
+
+/// Inserts a section for `version` above the previous releases. Entries
+/// written by hand under `Unreleased` are moved into the new section ahead
+/// of the generated ones, and issues they already mention are not added
+/// again. Released sections are left untouched. When the file ends with
+/// `compare` link definitions, `[Unreleased]` is moved to start at `tag`
+/// and a definition for the new version is added.
+pub fn update(content: &str, version: &str, tag: &str, date: &str, generated: &[Entry]) -> Result<Updated, AppError> {
+    let content = if content.trim().is_empty() { CHANGELOG_HEADER } else { content };
+    let lines: Vec<&str> = content.lines().collect();
+    if lines.iter().filter_map(|l| heading_version(l)).any(|v| v == version) {
+        return Err(AppError::Other(format!("Version {} is already in the changelog", version)));
+    }
+
+    let unreleased = lines
+        .iter()
+        .position(|l| heading_version(l).is_some_and(|v| v.eq_ignore_ascii_case("unreleased")));
+    let next_boundary = |from: usize| {
+        lines[from..]
+            .iter()
+            .position(|l| l.starts_with("## ") || is_link_definition(l))
+            .map_or(lines.len(), |i| from + i)
+    };
+    // 没有 Unreleased 时插入到第一个版本之前
+    let (start, end) = match unreleased {
+        Some(i) => (i + 1, next_boundary(i + 1)),
+        None => {
+            let end = next_boundary(0);
+            (end, end)
+        }
+    };
+
+    // Unreleased 中手写的内容：### 小节之外的文字保留在原处
+    let mut loose: Vec<&str> = Vec::new();
+    let mut written: Vec<(String, Vec<&str>)> = Vec::new();
+    for line in &lines[start..end] {
+        if let Some(name) = line.strip_prefix("### ") {
+            written.push((name.trim().to_string(), Vec::new()));
+        } else if !line.trim().is_empty() {
+            match written.last_mut() {
+                Some((_, items)) => items.push(line),
+                None => loose.push(line),
+            }
+        }
+    }
+    let written_text = lines[start..end].join("\n");
+
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    for (name, items) in written {
+        section_items(&mut sections, &name).extend(items.into_iter().map(str::to_string));
+    }
+    for entry in generated.iter().filter(|e| !mentions(&written_text, &e.key)) {
+        section_items(&mut sections, &entry.section).push(format!("- {}", entry.text));
+    }
+    sections.retain(|(_, items)| !items.is_empty());
+    sections.sort_by_key(|(name, _)| SECTIONS.iter().position(|s| s.eq_ignore_ascii_case(name)).unwrap_or(SECTIONS.len()));
+
+    let mut section = vec![format!("## [{}] - {}", version, date)];
+    for (name, items) in &sections {
+        section.push(String::new());
+        section.push(format!("### {}", name));
+        section.push(String::new());
+        section.extend(items.iter().cloned());
+    }
+
+    let mut out: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    trim_blank_end(&mut out);
+    if !out.is_empty() {
+        out.push(String::new());
+    }
+    if !loose.is_empty() {
+        out.extend(loose.iter().map(|l| l.to_string()));
+        out.push(String::new());
+    }
+    out.extend(section.iter().cloned());
+    out.push(String::new());
+    out.extend(lines[end..].iter().map(|l| l.to_string()));
+    trim_blank_end(&mut out);
+    update_links(&mut out, version, tag);
+
+    Ok(Updated {
+        content: out.join("\n") + "\n",
+        section: section.join("\n") + "\n",
+    })
+}
+
+fn section_items<'a>(sections: &'a mut Vec<(String, Vec<String>)>, name: &str) -> &'a mut Vec<String> {
+    let index = match sections.iter().position(|(n, _)| n.eq_ignore_ascii_case(name)) {
+        Some(index) => index,
+        None => {
+            sections.push((name.to_string(), Vec::new()));
+            sections.len() - 1
+        }
+    };
+    &mut sections[index].1
+}
+
+/// Version of a `## [1.2.0] - date` or `## 1.2.0` heading.
+fn heading_version(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("## ")?.trim();
+    match rest.strip_prefix('[') {
+        Some(rest) => rest.split(']').next(),
+        None => rest.split_whitespace().next(),
+    }
+}
+
+/// Link reference definitions such as `[1.2.0]: https://...` at the end of the file.
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+/// Points `[Unreleased]: .../compare/<previous>...HEAD` at the new tag and
+/// adds `[<version>]: .../compare/<previous>...<tag>` below it. Without an
+/// `Unreleased` definition, the new definition goes above the latest
+/// version's `compare` definition.
+fn update_links(lines: &mut Vec<String>, version: &str, tag: &str) {
+    let compare = |line: &str| {
+        let (label, url) = line.strip_prefix('[')?.split_once("]: ")?;
+        let (base, refs) = url.trim().rsplit_once("/compare/")?;
+        let (from, to) = refs.split_once("...")?;
+        Some((label.to_string(), base.to_string(), from.to_string(), to.to_string()))
+    };
+
+    let definitions: Vec<(usize, (String, String, String, String))> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_link_definition(line))
+        .filter_map(|(i, line)| compare(line).map(|c| (i, c)))
+        .collect();
+
+    if let Some((i, (label, base, from, _))) = definitions.iter().find(|(_, (label, ..))| label.eq_ignore_ascii_case("unreleased")) {
+        let i = *i;
+        lines[i] = format!("[{}]: {}/compare/{}...HEAD", label, base, tag);
+        lines.insert(i + 1, format!("[{}]: {}/compare/{}...{}", version, base, from, tag));
+    } else if let Some((i, (_, base, _, to))) = definitions.first() {
+        let i = *i;
+        let definition = format!("[{}]: {}/compare/{}...{}", version, base, to, tag);
+        lines.insert(i, definition);
+    }
+}
+
+/// Whether `text` mentions the key, so `PROJ-1` does not match `PROJ-12`.
+fn mentions(text: &str, key: &str) -> bool {
+    text.match_indices(key).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + key.len()..].chars().next();
+        !before.is_some_and(|c| c.is_ascii_alphanumeric()) && !after.is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+fn trim_blank_end(lines: &mut Vec<String>) {
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+}
@@ -0,0 +1,77 @@
+use crate::git::CommitInfo;
+use crate::message::fill_template;
+use crate::remote::Provider;
+use serde::Deserialize;
+
+/// Template used when `commit_comment.template` is not configured.
+pub const DEFAULT_COMMENT_TEMPLATE: &str = "{author} committed {short_hash} on branch {branch}:\n{subject}\n{url}";
+
+/// The `[commit_comment]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CommitCommentSettings {
+    /// Comment on the referenced issues after each commit.
+    pub enabled: bool,
+    pub template: String,
+    /// Remote whose URL is used for the commit link.
+    pub remote: String,
+    /// Hosting service of the remote; guessed from the host name when not set.
+    pub provider: Option<Provider>,
+}
+
+impl Default for CommitCommentSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            template: DEFAULT_COMMENT_TEMPLATE.to_string(),
+            remote: "origin".to_string(),
+            provider: None,
+        }
+    }
+}
+
+/// Renders the comment template.
+///
+/// Supported placeholders: `{hash}`, `{short_hash}`, `{subject}`, `{author}`,
+/// `{branch}` and `{url}` (the commit's web page). Lines whose placeholders
+/// are all empty, such as `{url}` for an unknown host, are left out. With
+/// `wiki`, values are escaped so JIRA wiki markup shows them literally.
+pub fn render_comment(template: &str, commit: &CommitInfo, branch: &str, url: Option<&str>, wiki: bool) -> String {
+    let escape = |value: &str| if wiki { escape_wiki(value) } else { value.to_string() };
+    let (subject, author, branch) = (escape(&commit.subject), escape(&commit.author), escape(branch));
+    let values = [
+        ("hash", commit.hash.as_str()),
+        ("short_hash", commit.short_hash.as_str()),
+        ("subject", subject.as_str()),
+        ("author", author.as_str()),
+        ("branch", branch.as_str()),
+        ("url", url.unwrap_or_default()),
+    ];
+
+    template
+        .lines()
+        .filter_map(|line| {
+            let has_placeholder = values.iter().any(|(name, _)| line.contains(&format!("{{{}}}", name)));
+            let rendered = fill_template(line, &values);
+            if has_placeholder && rendered.trim().is_empty() {
+                None
+            } else {
+                Some(rendered)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escapes characters that start JIRA wiki markup, such as the brackets of
+/// `[PROJ-12]` which would otherwise become a link.
+fn escape_wiki(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '[' | ']' | '{' | '}' | '*' | '_' | '|' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
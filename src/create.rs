@@ -0,0 +1,185 @@
+use crate::jira::{AllowedValue, FieldMeta, IssueTypeMeta};
+use crate::workflow::{allowed_value_json, allowed_value_label};
+use crate::AppError;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// The `[create]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct CreateSettings {
+    /// Project for new issues when `--project` is not given.
+    pub project: Option<String>,
+    /// Issue type when `--type` is not given.
+    pub issue_type: Option<String>,
+    /// Always create a branch for the new issue, as `--start` does.
+    pub start: bool,
+}
+
+/// An issue to create, as given on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct NewIssue {
+    pub project: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub components: Vec<String>,
+    pub priority: Option<String>,
+    /// Parent key, for subtasks.
+    pub parent: Option<String>,
+}
+
+/// Finds the issue type by name, case-insensitively.
+pub fn find_issue_type<'a>(types: &'a [IssueTypeMeta], name: &str) -> Result<&'a IssueTypeMeta, AppError> {
+    types.iter().find(|t| t.name.eq_ignore_ascii_case(name)).ok_or_else(|| {
+        let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+        AppError::Other(format!("Unknown issue type '{}'. Available: {}", name, names.join(", ")))
+    })
+}
+
+/// Builds the `fields` of the create request, checking the values against
+/// the create screen: fields must be on the screen and values among the
+/// allowed ones. `description` is already in the format of the API version.
+pub fn build_fields(
+    issue: &NewIssue,
+    issue_type: &IssueTypeMeta,
+    meta: &BTreeMap<String, FieldMeta>,
+    description: Option<Value>,
+) -> Result<Map<String, Value>, AppError> {
+    if issue.summary.trim().is_empty() {
+        return Err(AppError::Other("Summary must not be empty".to_string()));
+    }
+    if let Some(label) = issue.labels.iter().find(|l| l.chars().any(char::is_whitespace)) {
+        return Err(AppError::Other(format!("Labels cannot contain spaces: '{}'", label)));
+    }
+    if issue_type.subtask && issue.parent.is_none() {
+        return Err(AppError::Other(format!("{} is a subtask type, pass --parent", issue_type.name)));
+    }
+
+    let mut fields = Map::new();
+    fields.insert("project".to_string(), json!({ "key": issue.project }));
+    fields.insert("issuetype".to_string(), json!({ "id": issue_type.id }));
+    fields.insert("summary".to_string(), json!(issue.summary.trim()));
+
+    if let Some(description) = description {
+        fields.insert("description".to_string(), description);
+    }
+    if let Some(parent) = &issue.parent {
+        fields.insert("parent".to_string(), json!({ "key": parent }));
+    }
+    if !issue.labels.is_empty() {
+        screen_field(meta, "labels")?;
+        fields.insert("labels".to_string(), json!(issue.labels));
+    }
+    if !issue.components.is_empty() {
+        let field = screen_field(meta, "components")?;
+        let components = issue
+            .components
+            .iter()
+            .map(|c| checked_value(field, "component", c))
+            .collect::<Result<Vec<_>, _>>()?;
+        fields.insert("components".to_string(), Value::Array(components));
+    }
+    if let Some(priority) = &issue.priority {
+        let field = screen_field(meta, "priority")?;
+        fields.insert("priority".to_string(), checked_value(field, "priority", priority)?);
+    }
+
+    Ok(fields)
+}
+
+/// Required fields of the create screen that have no value yet and no default.
+pub fn missing_fields<'a>(meta: &'a BTreeMap<String, FieldMeta>, fields: &Map<String, Value>) -> Vec<(&'a str, &'a FieldMeta)> {
+    meta.iter()
+        .filter(|(id, field)| field.required && !field.has_default_value && !fields.contains_key(id.as_str()))
+        .map(|(id, field)| (id.as_str(), field))
+        .collect()
+}
+
+/// Converts text entered for a field into its JSON value, following the
+/// field schema: allowed values are matched by name, arrays take a comma
+/// separated list and numbers are parsed.
+pub fn field_value(field: &FieldMeta, input: &str) -> Result<Value, AppError> {
+    let schema = field.schema.clone().unwrap_or_default();
+    let is_array = schema.field_type == "array";
+    let element_type = if is_array { schema.items.unwrap_or_default() } else { schema.field_type };
+
+    let inputs: Vec<&str> = if is_array {
+        input.split(',').map(str::trim).filter(|s| !s.is_empty()).collect()
+    } else {
+        vec![input.trim()]
+    };
+    let values = inputs
+        .into_iter()
+        .map(|input| {
+            if !field.allowed_values.is_empty() {
+                return checked_value(field, &field.name, input);
+            }
+            match element_type.as_str() {
+                "string" | "" => Ok(json!(input)),
+                "number" => input
+                    .parse::<f64>()
+                    .map(|n| json!(n))
+                    .map_err(|_| AppError::Other(format!("{} must be a number", field.name))),
+                _ => Ok(json!({ "name": input })),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if is_array {
+        Ok(Value::Array(values))
+    } else {
+        Ok(values.into_iter().next().unwrap_or(Value::Null))
+    }
+}
+
+/// The JSON value for the allowed value chosen by index, as an array for
+/// array fields.
+pub fn selected_value(field: &FieldMeta, index: usize) -> Value {
+    let value = field.allowed_values.get(index).map_or(Value::Null, allowed_value_json);
+    let is_array = field.schema.as_ref().is_some_and(|schema| schema.field_type == "array");
+    if is_array {
+        Value::Array(vec![value])
+    } else {
+        value
+    }
+}
+
+fn screen_field<'a>(meta: &'a BTreeMap<String, FieldMeta>, id: &str) -> Result<&'a FieldMeta, AppError> {
+    // 元数据为空时（权限不足等）不做校验
+    if meta.is_empty() {
+        return Ok(&EMPTY_FIELD);
+    }
+    meta.get(id)
+        .ok_or_else(|| AppError::Other(format!("The create screen of this issue type has no '{}' field", id)))
+}
+
+static EMPTY_FIELD: FieldMeta = FieldMeta {
+    name: String::new(),
+    required: false,
+    has_default_value: false,
+    allowed_values: Vec::new(),
+    schema: None,
+};
+
+/// The value for `input` when it is one of the allowed values, by name when
+/// the field lists none.
+fn checked_value(field: &FieldMeta, what: &str, input: &str) -> Result<Value, AppError> {
+    if field.allowed_values.is_empty() {
+        return Ok(json!({ "name": input }));
+    }
+    find_allowed(&field.allowed_values, input).map(allowed_value_json).ok_or_else(|| {
+        let names: Vec<String> = field.allowed_values.iter().map(allowed_value_label).collect();
+        AppError::Other(format!("Unknown {} '{}'. Available: {}", what, input, names.join(", ")))
+    })
+}
+
+fn find_allowed<'a>(values: &'a [AllowedValue], input: &str) -> Option<&'a AllowedValue> {
+    values.iter().find(|v| {
+        [&v.name, &v.value, &v.id]
+            .into_iter()
+            .flatten()
+            .any(|candidate| candidate.eq_ignore_ascii_case(input))
+    })
+}
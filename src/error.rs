@@ -12,6 +12,10 @@ pub enum AppError {
     ConfigMissing,
     #[error("JIRA API error: {0}")]
     JiraApi(String),
+    #[error("Commit rejected: {0}")]
+    CommitRejected(String),
+    #[error("Audit failed: {0}")]
+    AuditFailed(String),
     #[error("Git error: {0}")]
     Git(String),
     #[error("Reqwest error: {0}")]
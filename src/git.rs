@@ -1,8 +1,30 @@
 use crate::AppError;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
+#[derive(Default)]
 pub struct GitOperations;
 
+/// A commit as shown in JIRA comments and reports.
+#[derive(Debug, Clone, Default)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub short_hash: String,
+    pub subject: String,
+    pub author: String,
+    /// Commit time in the local timezone, e.g. `2024-03-01T18:30:00.000+0800`.
+    pub committed_at: String,
+    /// Full commit message.
+    pub message: String,
+}
+
+/// `git log` format read by [`GitOperations::log`]: fields separated by NUL,
+/// commits by the record separator.
+const LOG_FORMAT: &str = "--format=%H%x00%h%x00%s%x00%an%x00%cd%x00%B%x1e";
+const LOG_DATE_FORMAT: &str = "--date=format-local:%Y-%m-%dT%H:%M:%S.000%z";
+
 impl GitOperations {
     pub fn new() -> Self {
         GitOperations
@@ -39,4 +61,274 @@ impl GitOperations {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Reads a value from git config, returning `None` when it is not set.
+    pub async fn get_config(&self, key: &str) -> Result<Option<String>, AppError> {
+        let output = Command::new("git")
+            .arg("config")
+            .arg("--get")
+            .arg(key)
+            .output()
+            .await
+            .map_err(|e| AppError::Git(e.to_string()))?;
+
+        // git config --get 在键不存在时返回 1
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(value).filter(|v| !v.is_empty()))
+    }
+
+    pub async fn set_config(&self, key: &str, value: &str) -> Result<(), AppError> {
+        let status = Command::new("git")
+            .arg("config")
+            .arg(key)
+            .arg(value)
+            .status()
+            .await
+            .map_err(|e| AppError::Git(e.to_string()))?;
+
+        if !status.success() {
+            return Err(AppError::Git(format!("Failed to set git config {}", key)));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the full messages of the most recent commits made on the
+    /// branch (see [`GitOperations::branch_commit_count`]), newest first.
+    pub async fn recent_commit_messages(&self, branch: &str, count: usize) -> Result<Vec<String>, AppError> {
+        let output = Command::new("git")
+            .arg("log")
+            .arg(format!("-n{}", count))
+            .arg("--format=%B%x00")
+            .arg("HEAD")
+            .args(own_commits_exclusions(branch))
+            .output()
+            .await
+            .map_err(|e| AppError::Git(e.to_string()))?;
+
+        // 新仓库还没有提交时 git log 会失败
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty())
+            .collect())
+    }
+
+    /// Adds trailers to a message with `git interpret-trailers`, so they are
+    /// merged into an existing trailer block (`Signed-off-by`, `Co-authored-by`)
+    /// and duplicates are skipped.
+    pub async fn add_trailers(&self, message: &str, trailers: &[(String, String)]) -> Result<String, AppError> {
+        if trailers.is_empty() {
+            return Ok(message.to_string());
+        }
+
+        let mut command = Command::new("git");
+        command.arg("interpret-trailers").arg("--if-exists").arg("addIfDifferent");
+        for (name, value) in trailers {
+            command.arg("--trailer").arg(format!("{}: {}", name, value));
+        }
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| AppError::Git(e.to_string()))?;
+
+        let mut stdin = child.stdin.take().ok_or_else(|| AppError::Git("Failed to open stdin".to_string()))?;
+        // 没有结尾换行时 git 会把 trailer 直接接在标题后面
+        stdin.write_all(format!("{}\n", message.trim_end()).as_bytes()).await?;
+        drop(stdin);
+
+        let output = child.wait_with_output().await.map_err(|e| AppError::Git(e.to_string()))?;
+        if !output.status.success() {
+            return Err(AppError::Git("git interpret-trailers failed".to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+    }
+
+    /// Runs git with the arguments and returns its trimmed stdout, or the
+    /// stderr as an error when git fails.
+    async fn git(&self, args: &[&str]) -> Result<String, AppError> {
+        let output = Command::new("git")
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| AppError::Git(e.to_string()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::Git(format!("git {} failed: {}", args.first().unwrap_or(&""), stderr.trim())));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+    }
+
+    pub async fn fetch(&self, remote: &str, branch: &str) -> Result<(), AppError> {
+        self.git(&["fetch", remote, branch]).await.map(|_| ())
+    }
+
+    pub async fn branch_exists(&self, branch: &str) -> Result<bool, AppError> {
+        let reference = format!("refs/heads/{}", branch);
+        Ok(self.git(&["show-ref", "--verify", "--quiet", &reference]).await.is_ok())
+    }
+
+    pub async fn tag_exists(&self, tag: &str) -> Result<bool, AppError> {
+        let reference = format!("refs/tags/{}", tag);
+        Ok(self.git(&["show-ref", "--verify", "--quiet", &reference]).await.is_ok())
+    }
+
+    /// Tags reachable from HEAD.
+    pub async fn merged_tags(&self) -> Result<Vec<String>, AppError> {
+        let output = self.git(&["tag", "--merged", "HEAD"]).await?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    /// Creates an annotated tag on HEAD.
+    pub async fn create_tag(&self, tag: &str, message: &str) -> Result<(), AppError> {
+        self.git(&["tag", "-a", tag, "-m", message]).await.map(|_| ())
+    }
+
+    pub async fn checkout(&self, branch: &str) -> Result<(), AppError> {
+        self.git(&["checkout", branch]).await.map(|_| ())
+    }
+
+    /// Creates a branch and checks it out, starting from `start_point` or HEAD.
+    pub async fn checkout_new_branch(&self, branch: &str, start_point: Option<&str>) -> Result<(), AppError> {
+        let mut args = vec!["checkout", "-b", branch];
+        if let Some(start_point) = start_point {
+            // 不追踪远程基础分支，推送时再设置 upstream
+            args.extend(["--no-track", start_point]);
+        }
+        self.git(&args).await.map(|_| ())
+    }
+
+    /// Counts the commits on HEAD that are on no other local branch and on no
+    /// remote branch other than the branch's own, i.e. the commits made on
+    /// this branch so far.
+    pub async fn branch_commit_count(&self, branch: &str) -> Result<usize, AppError> {
+        let mut args = vec!["rev-list".to_string(), "--count".to_string(), "HEAD".to_string()];
+        args.extend(own_commits_exclusions(branch));
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let count = self.git(&args).await?;
+        count.trim().parse().map_err(|_| AppError::Git(format!("Unexpected git rev-list output: {}", count)))
+    }
+
+    /// Runs `git push` with the arguments, attached to the terminal so git
+    /// can ask for credentials and show progress.
+    pub async fn push(&self, args: &[String]) -> Result<(), AppError> {
+        let status = Command::new("git")
+            .arg("push")
+            .args(args)
+            .status()
+            .await
+            .map_err(|e| AppError::Git(e.to_string()))?;
+
+        if !status.success() {
+            return Err(AppError::Git("git push failed".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the commit HEAD points to.
+    pub async fn head_commit(&self) -> Result<CommitInfo, AppError> {
+        self.log(&["-1"])
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::Git("No commit found".to_string()))
+    }
+
+    /// Runs `git log` with the revision arguments and returns the commits,
+    /// newest first.
+    pub async fn log(&self, args: &[&str]) -> Result<Vec<CommitInfo>, AppError> {
+        let log_args = log_command(args, &[LOG_DATE_FORMAT, LOG_FORMAT]);
+        let output = self.git(&log_args).await?;
+
+        Ok(output
+            .split('\x1e')
+            .map(|record| record.trim_start_matches('\n'))
+            .filter(|record| !record.is_empty())
+            .map(|record| {
+                let mut parts = record.splitn(6, '\0').map(str::to_string);
+                CommitInfo {
+                    hash: parts.next().unwrap_or_default(),
+                    short_hash: parts.next().unwrap_or_default(),
+                    subject: parts.next().unwrap_or_default(),
+                    author: parts.next().unwrap_or_default(),
+                    committed_at: parts.next().unwrap_or_default(),
+                    message: parts.next().unwrap_or_default().trim().to_string(),
+                }
+            })
+            .collect())
+    }
+
+    /// Lines of `git log --graph` as the graph drawing and the hash of the
+    /// commit on the line; lines that only continue the graph have none.
+    pub async fn log_graph(&self, args: &[&str]) -> Result<Vec<(String, Option<String>)>, AppError> {
+        let log_args = log_command(args, &["--graph", "--format=%x1f%H"]);
+        let output = self.git(&log_args).await?;
+
+        Ok(output
+            .lines()
+            .map(|line| match line.split_once('\x1f') {
+                Some((graph, hash)) => (graph.to_string(), Some(hash.to_string())),
+                None => (line.trim_end().to_string(), None),
+            })
+            .collect())
+    }
+
+    /// Commits on HEAD that a push of the branch would send: those after the
+    /// upstream, or the branch's own commits when it has no upstream yet.
+    pub async fn unpushed_commits(&self, branch: &str) -> Result<Vec<CommitInfo>, AppError> {
+        if self.git(&["rev-parse", "--verify", "--quiet", "@{upstream}"]).await.is_ok() {
+            return self.log(&["@{upstream}..HEAD"]).await;
+        }
+        let exclude_local = format!("--exclude={}", branch);
+        let exclude_remote = format!("--exclude=*/{}", branch);
+        self.log(&["HEAD", "--not", &exclude_local, "--branches", &exclude_remote, "--remotes"]).await
+    }
+
+    /// Returns the URL of the remote, or `None` when it does not exist.
+    pub async fn remote_url(&self, remote: &str) -> Result<Option<String>, AppError> {
+        Ok(self.git(&["remote", "get-url", remote]).await.ok())
+    }
+
+    /// Resolves a path inside the git directory, e.g. for state files.
+    pub async fn git_path(&self, name: &str) -> Result<PathBuf, AppError> {
+        Ok(PathBuf::from(self.git(&["rev-parse", "--git-path", name]).await?))
+    }
+}
+
+/// `git log` with the caller's arguments followed by the fixed options, so
+/// the fixed format wins over `--oneline` or `--format` given by the user.
+/// The fixed options go before a `--` separating the paths.
+fn log_command<'a>(args: &[&'a str], fixed: &[&'a str]) -> Vec<&'a str> {
+    let paths = args.iter().position(|arg| *arg == "--").unwrap_or(args.len());
+    let mut command = vec!["log"];
+    command.extend_from_slice(&args[..paths]);
+    command.extend_from_slice(fixed);
+    command.extend_from_slice(&args[paths..]);
+    command
+}
+
+/// Revision arguments that, after `HEAD`, leave only the commits made on the
+/// branch: everything on other local branches and on remote branches other
+/// than the branch's own is excluded.
+fn own_commits_exclusions(branch: &str) -> Vec<String> {
+    vec![
+        "--not".to_string(),
+        format!("--exclude={}", branch),
+        "--branches".to_string(),
+        format!("--exclude=*/{}", branch),
+        "--remotes".to_string(),
+    ]
+}
@@ -0,0 +1,160 @@
+use crate::git::CommitInfo;
+use crate::issue_key::KeyExtractor;
+use crate::AppError;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Comment written by `sync-history` in comment mode; the branch of old
+/// commits is unknown.
+pub const HISTORY_COMMENT_TEMPLATE: &str = "{author} committed {hash}:\n{subject}\n{url}";
+
+/// What `sync-history` creates on the issues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    Link,
+    Comment,
+}
+
+impl SyncMode {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value {
+            "link" => Ok(SyncMode::Link),
+            "comment" => Ok(SyncMode::Comment),
+            other => Err(AppError::Other(format!("Unknown sync mode '{}', expected link or comment", other))),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            SyncMode::Link => "link",
+            SyncMode::Comment => "comment",
+        }
+    }
+}
+
+/// A commit and the issue keys its message refers to.
+#[derive(Debug, Clone)]
+pub struct CommitRefs {
+    pub commit: CommitInfo,
+    pub keys: Vec<String>,
+}
+
+/// Pairs every commit that mentions issues with their keys.
+pub fn collect_refs(commits: Vec<CommitInfo>, extractor: &KeyExtractor) -> Vec<CommitRefs> {
+    commits
+        .into_iter()
+        .filter_map(|commit| {
+            let keys = extractor.extract_from_text(&commit.message);
+            (!keys.is_empty()).then_some(CommitRefs { commit, keys })
+        })
+        .collect()
+}
+
+/// Distinct keys of all commits, in order of first appearance.
+pub fn distinct_keys(refs: &[CommitRefs]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    refs.iter()
+        .flat_map(|r| r.keys.iter())
+        .filter(|key| seen.insert(key.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Commits already synced, stored one `<mode> <hash>` per line so an
+/// interrupted run can resume where it stopped.
+pub struct Checkpoint {
+    mode: SyncMode,
+    done: HashSet<String>,
+}
+
+impl Checkpoint {
+    /// Reads the checkpoint file; a missing file means nothing was synced yet.
+    pub fn load(path: &Path, mode: SyncMode) -> Result<Self, AppError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let done = content
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .filter(|(line_mode, _)| *line_mode == mode.name())
+            .map(|(_, hash)| hash.trim().to_string())
+            .collect();
+        Ok(Self { mode, done })
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.done.contains(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+
+    /// Records a synced commit, appending it to the file right away.
+    pub fn record(&mut self, path: &Path, hash: &str) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{} {}", self.mode.name(), hash)?;
+        self.done.insert(hash.to_string());
+        Ok(())
+    }
+}
+
+/// Counts and problems of a `sync-history` run.
+#[derive(Debug, Default)]
+pub struct SyncSummary {
+    pub dry_run: bool,
+    pub commits_scanned: usize,
+    pub commits_with_keys: usize,
+    pub commits_resumed: usize,
+    pub created: usize,
+    pub already_present: usize,
+    /// Keys that do not exist, with the commits referring to them.
+    pub unknown_keys: BTreeMap<String, Vec<String>>,
+    /// Keys that could not be read, with the reason.
+    pub inaccessible_keys: BTreeMap<String, String>,
+    /// Commits that failed, with the error.
+    pub failures: Vec<(String, String)>,
+}
+
+impl fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let created = if self.dry_run { "Would create" } else { "Created" };
+        writeln!(f, "Commits scanned:        {}", self.commits_scanned)?;
+        writeln!(f, "Commits with keys:      {}", self.commits_with_keys)?;
+        writeln!(f, "Skipped (checkpoint):   {}", self.commits_resumed)?;
+        writeln!(f, "{:<24}{}", format!("{}:", created), self.created)?;
+        write!(f, "Already present:        {}", self.already_present)?;
+
+        if !self.unknown_keys.is_empty() {
+            write!(f, "\nUnknown keys:")?;
+            for (key, commits) in &self.unknown_keys {
+                write!(f, "\n  {} ({})", key, commits.join(", "))?;
+            }
+        }
+        if !self.inaccessible_keys.is_empty() {
+            write!(f, "\nInaccessible keys:")?;
+            for (key, reason) in &self.inaccessible_keys {
+                write!(f, "\n  {}: {}", key, reason)?;
+            }
+        }
+        if !self.failures.is_empty() {
+            write!(f, "\nFailed commits (run again to retry):")?;
+            for (hash, error) in &self.failures {
+                write!(f, "\n  {}: {}", hash, error)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use crate::issue::IssueStatus;
+use crate::issue_key::{KeyExtractionSettings, KeyExtractor};
+use crate::AppError;
+use regex::Regex;
+use serde::Deserialize;
+
+/// Where the JIRA key has to appear in the commit message.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyPosition {
+    /// Anywhere in the subject or body.
+    #[default]
+    Anywhere,
+    /// Somewhere in the first line.
+    Subject,
+    /// At the start of the first line, optionally in brackets (`[PROJ-1] ...`).
+    Prefix,
+}
+
+/// What to do when JIRA cannot be reached during verification.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OfflinePolicy {
+    Allow,
+    #[default]
+    Warn,
+    Block,
+}
+
+/// The `[commit_msg]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct CommitMsgSettings {
+    /// Accepted project keys. Falls back to `jira_id_prefix` when empty.
+    pub projects: Vec<String>,
+    pub position: KeyPosition,
+    /// Regexes matched against the subject; matching commits need no key.
+    pub exempt: Vec<String>,
+    /// Ask JIRA whether the referenced issues exist and are still open.
+    pub verify: bool,
+    /// Status names treated as closed. When empty, the "done" status category is used.
+    pub closed_statuses: Vec<String>,
+    pub offline: OfflinePolicy,
+    pub timeout_secs: u64,
+}
+
+impl Default for CommitMsgSettings {
+    fn default() -> Self {
+        Self {
+            projects: Vec::new(),
+            position: KeyPosition::default(),
+            exempt: vec![
+                "^Merge ".to_string(),
+                "^Revert ".to_string(),
+                "^fixup! ".to_string(),
+                "^squash! ".to_string(),
+                "^amend! ".to_string(),
+            ],
+            verify: false,
+            closed_statuses: Vec::new(),
+            offline: OfflinePolicy::default(),
+            timeout_secs: 5,
+        }
+    }
+}
+
+/// Drops the comment lines git adds to the message file, including everything
+/// below the `git commit -v` scissors line.
+pub fn strip_comments(message: &str) -> String {
+    message
+        .lines()
+        .take_while(|line| !line.starts_with("# ------------------------ >8 ------------------------"))
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Checks a commit message against the configured rules and returns the
+/// accepted JIRA keys. Exempt messages yield an empty list.
+pub fn validate_message(message: &str, settings: &CommitMsgSettings) -> Result<Vec<String>, AppError> {
+    let message = strip_comments(message);
+    let subject = message.lines().next().unwrap_or_default();
+
+    if message.is_empty() {
+        // git aborts empty commits on its own
+        return Ok(Vec::new());
+    }
+
+    for pattern in &settings.exempt {
+        let re = Regex::new(pattern)
+            .map_err(|e| AppError::ConfigString(format!("Invalid exempt pattern '{}': {}", pattern, e)))?;
+        if re.is_match(subject) {
+            return Ok(Vec::new());
+        }
+    }
+
+    let extractor = KeyExtractor::new(&KeyExtractionSettings {
+        projects: settings.projects.clone(),
+        ..KeyExtractionSettings::default()
+    })?;
+    let keys = match settings.position {
+        KeyPosition::Anywhere => extractor.extract_from_text(&message),
+        KeyPosition::Subject => extractor.extract_from_text(subject),
+        KeyPosition::Prefix => leading_keys(subject, &extractor),
+    };
+
+    if keys.is_empty() {
+        let projects = if settings.projects.is_empty() {
+            "any project".to_string()
+        } else {
+            settings.projects.join(", ")
+        };
+        let place = match settings.position {
+            KeyPosition::Anywhere => "in the message",
+            KeyPosition::Subject => "in the subject line",
+            KeyPosition::Prefix => "at the start of the subject line",
+        };
+        return Err(AppError::CommitRejected(format!("no JIRA key for {} found {}", projects, place)));
+    }
+
+    Ok(keys)
+}
+
+/// Returns true when the status counts as closed for the hook.
+pub fn is_closed(status: &IssueStatus, settings: &CommitMsgSettings) -> bool {
+    if settings.closed_statuses.is_empty() {
+        status.category.as_ref().is_some_and(|c| c.key == "done")
+    } else {
+        settings.closed_statuses.iter().any(|s| s.eq_ignore_ascii_case(&status.name))
+    }
+}
+
+/// Collects the keys at the start of the subject, each optionally in
+/// brackets (`[PROJ-1][PROJ-2] ...`, `PROJ-1: ...`).
+fn leading_keys(subject: &str, extractor: &KeyExtractor) -> Vec<String> {
+    let is_separator = |c: char| c.is_whitespace() || matches!(c, '[' | ']' | ':');
+    let mut keys: Vec<String> = Vec::new();
+    let mut rest = subject;
+    loop {
+        rest = rest.trim_start_matches(is_separator);
+        let token = &rest[..rest.find(is_separator).unwrap_or(rest.len())];
+        let is_key_token = token.ends_with(|c: char| c.is_ascii_digit())
+            && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        match extractor.extract_from_text(token).as_slice() {
+            [key] if is_key_token => {
+                if !keys.contains(key) {
+                    keys.push(key.clone());
+                }
+            }
+            _ => return keys,
+        }
+        rest = &rest[token.len()..];
+    }
+}
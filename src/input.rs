@@ -10,10 +10,9 @@ pub fn prompt_for_input(prompt: &str, default: Option<&str>) -> io::Result<Strin
     io::stdin().read_line(&mut input)?;
     let input = input.trim();
 
-    if input.is_empty() && default.is_some() {
-        Ok(default.unwrap().to_string())
-    } else {
-        Ok(input.to_string())
+    match default {
+        Some(default) if input.is_empty() => Ok(default.to_string()),
+        _ => Ok(input.to_string()),
     }
 }
 
@@ -31,4 +30,76 @@ pub fn confirm_commit(commit_message: &str) -> bool {
     println!("Git commit command: git commit -m \"{}\"", commit_message);
     let input = prompt_for_input("Do you want to proceed? (y/n)", Some("y")).unwrap_or_default();
     matches!(input.to_lowercase().as_str(), "y" | "yes" | "")
-}
\ No newline at end of file
+}
+
+/// Lets the user pick one or several of the listed items. Accepts a comma
+/// separated list of numbers or `all`, and returns the chosen indices.
+pub fn select_items(prompt: &str, items: &[String], default: &str) -> io::Result<Vec<usize>> {
+    for (i, item) in items.iter().enumerate() {
+        println!("  {}) {}", i + 1, item);
+    }
+
+    loop {
+        let input = prompt_for_input(prompt, Some(default))?;
+        if input.eq_ignore_ascii_case("all") {
+            return Ok((0..items.len()).collect());
+        }
+
+        let selection: Option<Vec<usize>> = input
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<usize>().ok().filter(|n| (1..=items.len()).contains(n)).map(|n| n - 1))
+            .collect();
+
+        match selection {
+            Some(indices) if !indices.is_empty() => {
+                let mut unique = Vec::new();
+                for index in indices {
+                    if !unique.contains(&index) {
+                        unique.push(index);
+                    }
+                }
+                return Ok(unique);
+            }
+            _ => println!("Please enter numbers between 1 and {}, or 'all'.", items.len()),
+        }
+    }
+}
+
+/// Lets the user pick exactly one of the listed items and returns its index.
+pub fn select_item(prompt: &str, items: &[String], default: &str) -> io::Result<usize> {
+    for (i, item) in items.iter().enumerate() {
+        println!("  {}) {}", i + 1, item);
+    }
+
+    loop {
+        let input = prompt_for_input(prompt, Some(default))?;
+        match input.parse::<usize>() {
+            Ok(n) if (1..=items.len()).contains(&n) => return Ok(n - 1),
+            _ => println!("Please enter a number between 1 and {}.", items.len()),
+        }
+    }
+}
+
+/// Opens `$VISUAL` or `$EDITOR` on a temporary file holding `initial` and
+/// returns the saved text. The editor command may include arguments, e.g.
+/// `code --wait`.
+pub fn edit_text(initial: &str) -> io::Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    let mut file = tempfile::Builder::new().prefix("jira_git_helper_").suffix(".txt").tempfile()?;
+    file.write_all(initial.as_bytes())?;
+    file.flush()?;
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = std::process::Command::new(program).args(parts).arg(file.path()).status();
+    // 编辑器可能替换文件，按路径重新读取；file 离开作用域时删除
+    let text = std::fs::read_to_string(file.path());
+
+    if !status?.success() {
+        return Err(io::Error::other(format!("Editor '{}' exited with an error", editor)));
+    }
+    Ok(text?.trim().to_string())
+}
@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Fields requested from JIRA for an [`Issue`], passed as `fields=` to keep
+/// responses small.
+pub const ISSUE_FIELDS: &[&str] = &[
+    "summary",
+    "issuetype",
+    "status",
+    "priority",
+    "assignee",
+    "reporter",
+    "labels",
+    "components",
+    "fixVersions",
+    "parent",
+    "subtasks",
+    "description",
+];
+
+/// A JIRA issue with the fields this tool and its templates use. It is
+/// (de)serialized in JIRA's `{ "key", "fields": { ... } }` shape, so JSON
+/// written by `list --json` can be read back.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(from = "RawIssue", into = "RawIssue")]
+pub struct Issue {
+    pub key: String,
+    pub summary: String,
+    pub issue_type: Option<String>,
+    pub status: Option<IssueStatus>,
+    pub priority: Option<String>,
+    pub assignee: Option<User>,
+    pub reporter: Option<User>,
+    pub labels: Vec<String>,
+    pub components: Vec<String>,
+    pub fix_versions: Vec<String>,
+    pub parent: Option<LinkedIssue>,
+    /// Epic key, read from the field configured as `epic_link_field`, see
+    /// [`Issue::resolve_epic`].
+    pub epic: Option<String>,
+    pub subtasks: Vec<LinkedIssue>,
+    pub description: Option<RichText>,
+    /// Custom fields returned by JIRA, by field ID.
+    pub custom_fields: HashMap<String, Value>,
+}
+
+impl Issue {
+    pub fn new(key: &str, summary: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            summary: summary.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn status_name(&self) -> &str {
+        self.status.as_ref().map_or("", |s| s.name.as_str())
+    }
+
+    pub fn assignee_name(&self) -> &str {
+        self.assignee.as_ref().map_or("", |u| u.display_name.as_str())
+    }
+
+    /// Sets `epic` from the epic link custom field, when it holds an issue key.
+    pub fn resolve_epic(&mut self, epic_link_field: &str) {
+        self.epic = self
+            .custom_fields
+            .get(epic_link_field)
+            .and_then(Value::as_str)
+            .filter(|v| is_issue_key(v))
+            .map(str::to_string);
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct IssueStatus {
+    pub name: String,
+    #[serde(rename = "statusCategory")]
+    pub category: Option<StatusCategory>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct StatusCategory {
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    #[serde(default)]
+    pub display_name: String,
+    /// Username on JIRA Server / Data Center.
+    pub name: Option<String>,
+    /// Account ID on JIRA Cloud.
+    pub account_id: Option<String>,
+    pub email_address: Option<String>,
+}
+
+/// A parent or subtask reference.
+#[derive(Debug, Clone)]
+pub struct LinkedIssue {
+    pub key: String,
+    pub summary: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Comment {
+    pub author: Option<User>,
+    pub body: RichText,
+    #[serde(default)]
+    pub created: String,
+}
+
+/// Rich text as returned by JIRA: wiki markup (API v2) or an Atlassian
+/// Document Format tree (API v3).
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum RichText {
+    Wiki(String),
+    Adf(Value),
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawIssue {
+    #[serde(default)]
+    key: String,
+    fields: RawFields,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFields {
+    #[serde(default)]
+    summary: String,
+    issuetype: Option<Named>,
+    status: Option<IssueStatus>,
+    priority: Option<Named>,
+    assignee: Option<User>,
+    reporter: Option<User>,
+    #[serde(default)]
+    labels: Vec<String>,
+    #[serde(default)]
+    components: Vec<Named>,
+    #[serde(default)]
+    fix_versions: Vec<Named>,
+    parent: Option<RawLinkedIssue>,
+    #[serde(default)]
+    subtasks: Vec<RawLinkedIssue>,
+    description: Option<RichText>,
+    // 自定义字段（如 Epic Link）
+    #[serde(flatten)]
+    custom: HashMap<String, Value>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Named {
+    name: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawLinkedIssue {
+    key: String,
+    #[serde(default)]
+    fields: Option<RawLinkedFields>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RawLinkedFields {
+    #[serde(default)]
+    summary: String,
+}
+
+impl From<RawLinkedIssue> for LinkedIssue {
+    fn from(raw: RawLinkedIssue) -> Self {
+        LinkedIssue {
+            key: raw.key,
+            summary: raw.fields.map(|f| f.summary).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<RawIssue> for Issue {
+    fn from(raw: RawIssue) -> Self {
+        let fields = raw.fields;
+        Issue {
+            key: raw.key,
+            summary: fields.summary,
+            issue_type: fields.issuetype.map(|t| t.name),
+            status: fields.status,
+            priority: fields.priority.map(|p| p.name),
+            assignee: fields.assignee,
+            reporter: fields.reporter,
+            labels: fields.labels,
+            components: fields.components.into_iter().map(|c| c.name).collect(),
+            fix_versions: fields.fix_versions.into_iter().map(|v| v.name).collect(),
+            parent: fields.parent.map(LinkedIssue::from),
+            epic: None,
+            subtasks: fields.subtasks.into_iter().map(LinkedIssue::from).collect(),
+            description: fields.description,
+            custom_fields: fields
+                .custom
+                .into_iter()
+                .filter(|(name, _)| name.starts_with("customfield_"))
+                .collect(),
+        }
+    }
+}
+
+impl From<LinkedIssue> for RawLinkedIssue {
+    fn from(issue: LinkedIssue) -> Self {
+        RawLinkedIssue {
+            key: issue.key,
+            fields: Some(RawLinkedFields { summary: issue.summary }),
+        }
+    }
+}
+
+impl From<Issue> for RawIssue {
+    fn from(issue: Issue) -> Self {
+        let named = |name: String| Named { name };
+        RawIssue {
+            key: issue.key,
+            fields: RawFields {
+                summary: issue.summary,
+                issuetype: issue.issue_type.map(named),
+                status: issue.status,
+                priority: issue.priority.map(named),
+                assignee: issue.assignee,
+                reporter: issue.reporter,
+                labels: issue.labels,
+                components: issue.components.into_iter().map(named).collect(),
+                fix_versions: issue.fix_versions.into_iter().map(named).collect(),
+                parent: issue.parent.map(RawLinkedIssue::from),
+                subtasks: issue.subtasks.into_iter().map(RawLinkedIssue::from).collect(),
+                description: issue.description,
+                custom: issue.custom_fields,
+            },
+        }
+    }
+}
+
+fn is_issue_key(value: &str) -> bool {
+    value
+        .split_once('-')
+        .is_some_and(|(project, number)| !project.is_empty() && !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+}
@@ -0,0 +1,194 @@
+use crate::{app_config, AppError};
+use config::Config;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+
+/// A named regex with a `number` capture group and an optional `project`
+/// group. Matches without a project use the default project.
+#[derive(Deserialize, Debug, Clone)]
+pub struct KeyRule {
+    pub name: String,
+    pub pattern: String,
+}
+
+/// The `[key_extraction]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct KeyExtractionSettings {
+    pub rules: Vec<KeyRule>,
+    /// Accepted project keys. Falls back to `jira_id_prefix`; empty accepts any project.
+    pub projects: Vec<String>,
+    /// Project used for bare issue numbers. Falls back to `jira_id_prefix`.
+    pub default_project: Option<String>,
+}
+
+impl Default for KeyExtractionSettings {
+    fn default() -> Self {
+        Self {
+            rules: default_rules(),
+            projects: Vec::new(),
+            default_project: None,
+        }
+    }
+}
+
+/// The built-in rules: upper-case keys like `PROJ-123`, `AB2-17` and
+/// `MY_PROJ-4` anywhere in the name, and a bare `123-fix-login` at the start
+/// of the last path segment. Lower-case and `_`-separated keys such as
+/// `proj_123` are only recognised for the configured `projects`, see
+/// [`KeyExtractor::new`].
+pub fn default_rules() -> Vec<KeyRule> {
+    vec![
+        KeyRule {
+            name: "key".to_string(),
+            pattern: r"(?:^|[^A-Za-z0-9])(?P<project>[A-Z][A-Z0-9_]*)-(?P<number>\d+)".to_string(),
+        },
+        KeyRule {
+            name: "bare-number".to_string(),
+            pattern: r"(?:^|/)(?P<number>\d+)(?:[-_]|$)".to_string(),
+        },
+    ]
+}
+
+struct CompiledRule {
+    name: String,
+    regex: Regex,
+}
+
+/// Extracts normalized JIRA keys (`PROJECT-123`) from branch names.
+pub struct KeyExtractor {
+    rules: Vec<CompiledRule>,
+    projects: Vec<String>,
+    default_project: Option<String>,
+}
+
+impl KeyExtractor {
+    /// Compiles the rules. When `projects` is set, an extra rule matches
+    /// those projects in any case and with `-` or `_` (`proj_123`).
+    pub fn new(settings: &KeyExtractionSettings) -> Result<Self, AppError> {
+        let mut rules = settings.rules.clone();
+        if !settings.projects.is_empty() {
+            let projects: Vec<String> = settings.projects.iter().map(|p| regex::escape(p.trim())).collect();
+            rules.push(KeyRule {
+                name: "projects".to_string(),
+                pattern: format!(
+                    r"(?i)(?:^|[^A-Za-z0-9])(?P<project>{})[-_](?P<number>\d+)",
+                    projects.join("|")
+                ),
+            });
+        }
+
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let regex = Regex::new(&rule.pattern)
+                    .map_err(|e| AppError::ConfigString(format!("Invalid key rule '{}': {}", rule.name, e)))?;
+                if !regex.capture_names().any(|n| n == Some("number")) {
+                    return Err(AppError::ConfigString(format!(
+                        "Key rule '{}' has no 'number' capture group",
+                        rule.name
+                    )));
+                }
+                Ok(CompiledRule { name: rule.name.clone(), regex })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        Ok(Self {
+            rules,
+            projects: settings.projects.iter().map(|p| normalize_project(p)).collect(),
+            default_project: settings.default_project.as_deref().map(normalize_project),
+        })
+    }
+
+    /// Builds the extractor from the `[key_extraction]` section, using
+    /// `jira_id_prefix` as the accepted and default project when not set.
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        let mut settings: KeyExtractionSettings = app_config::get_section(config, "key_extraction")?;
+        if let Ok(prefix) = config.get_string("jira_id_prefix") {
+            if !prefix.is_empty() {
+                if settings.projects.is_empty() {
+                    settings.projects.push(prefix.clone());
+                }
+                settings.default_project.get_or_insert(prefix);
+            }
+        }
+        Self::new(&settings)
+    }
+
+    /// Returns every distinct key in the branch name, in order of appearance.
+    pub fn extract(&self, branch_name: &str) -> Vec<String> {
+        self.extract_with(branch_name, |_| true)
+    }
+
+    /// Like [`KeyExtractor::extract`], but for free text such as commit
+    /// messages: only rules with a `project` group apply, so bare numbers
+    /// are not mistaken for issues.
+    pub fn extract_from_text(&self, text: &str) -> Vec<String> {
+        self.extract_with(text, |rule| rule.regex.capture_names().any(|n| n == Some("project")))
+    }
+
+    fn extract_with(&self, text: &str, applies: impl Fn(&CompiledRule) -> bool) -> Vec<String> {
+        let mut found: Vec<(usize, String)> = Vec::new();
+        for rule in self.rules.iter().filter(|rule| applies(rule)) {
+            for caps in rule.regex.captures_iter(text) {
+                let number = &caps["number"];
+                let project = match caps.name("project") {
+                    Some(project) => normalize_project(project.as_str()),
+                    None => match &self.default_project {
+                        Some(project) => project.clone(),
+                        None => {
+                            log::debug!("Rule '{}' matched {} but no default project is set", rule.name, number);
+                            continue;
+                        }
+                    },
+                };
+                if !self.accepts(&project) {
+                    continue;
+                }
+                // 去掉前导零，使 PROJ-007 与 PROJ-7 一致
+                let number = number.trim_start_matches('0');
+                let number = if number.is_empty() { "0" } else { number };
+                let start = caps.get(0).map_or(0, |m| m.start());
+                found.push((start, format!("{}-{}", project, number)));
+            }
+        }
+
+        found.sort_by_key(|(start, _)| *start);
+        let mut keys: Vec<String> = Vec::new();
+        for (_, key) in found {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+
+    /// Exact, case-insensitive match against the accepted projects.
+    pub fn accepts(&self, project: &str) -> bool {
+        self.projects.is_empty() || self.projects.iter().any(|p| p.eq_ignore_ascii_case(project))
+    }
+}
+
+/// Parses an issue given on the command line, either as a key or as a pasted
+/// browse URL (`https://jira.example.com/browse/PROJ-123`, or a board link
+/// with `selectedIssue=PROJ-123`).
+pub fn parse_issue_reference(input: &str) -> Option<String> {
+    lazy_static! {
+        static ref KEY_RE: Regex = Regex::new(r"^[A-Z][A-Z0-9_]*-\d+$").unwrap();
+    }
+    let input = input.trim();
+    let candidate = if let Some((_, rest)) = input.split_once("/browse/") {
+        rest.split(['/', '?', '#']).next().unwrap_or_default()
+    } else if let Some((_, rest)) = input.split_once("selectedIssue=") {
+        rest.split('&').next().unwrap_or_default()
+    } else {
+        input
+    };
+    let key = candidate.to_uppercase();
+    KEY_RE.is_match(&key).then_some(key)
+}
+
+fn normalize_project(project: &str) -> String {
+    project.trim().to_uppercase()
+}
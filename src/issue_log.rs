@@ -0,0 +1,201 @@
+use crate::git::CommitInfo;
+use crate::issue::{Issue, User};
+use crate::message::{display_width, truncate_at_word};
+use crate::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The `[log]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LogSettings {
+    /// Keep looked up issues in a cache file in the git directory.
+    pub cache: bool,
+    /// Seconds a cached issue is used before it is fetched again.
+    pub cache_ttl: u64,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self { cache: true, cache_ttl: 300 }
+    }
+}
+
+/// `git log` options that add output the commit parser cannot read, such as
+/// diffs, stats or a graph. `--graph` has its own flag on `log`.
+const OUTPUT_OPTIONS: &[&str] = &[
+    "-p", "-u", "--patch", "--graph", "--stat", "--numstat", "--shortstat", "--dirstat", "--name-only",
+    "--name-status", "--raw", "--summary", "--check", "-z",
+];
+
+/// Rejects passthrough arguments that change the shape of `git log` output.
+/// Format options such as `--oneline` are allowed; the fixed format overrides them.
+pub fn check_git_args(args: &[String]) -> Result<(), AppError> {
+    let options = args.iter().take_while(|arg| *arg != "--");
+    for arg in options {
+        let name = arg.split('=').next().unwrap_or_default();
+        if OUTPUT_OPTIONS.contains(&name) {
+            return Err(AppError::Other(format!("'{}' is not supported by log", arg)));
+        }
+    }
+    Ok(())
+}
+
+/// The fields of an issue shown by `log`, as stored in the cache.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CachedIssue {
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<User>,
+    /// Unix time of the lookup.
+    pub fetched_at: u64,
+}
+
+impl CachedIssue {
+    pub fn new(issue: &Issue, fetched_at: u64) -> Self {
+        Self {
+            summary: issue.summary.clone(),
+            status: issue.status_name().to_string(),
+            assignee: issue.assignee.clone(),
+            fetched_at,
+        }
+    }
+}
+
+/// Issues looked up by earlier runs, stored as JSON by key.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct IssueCache {
+    issues: HashMap<String, CachedIssue>,
+}
+
+impl IssueCache {
+    /// Reads the cache; a missing or unreadable file gives an empty cache.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The cached issue when it is younger than `ttl` seconds.
+    pub fn get(&self, key: &str, now: u64, ttl: u64) -> Option<&CachedIssue> {
+        self.issues.get(key).filter(|issue| now.saturating_sub(issue.fetched_at) < ttl)
+    }
+
+    pub fn insert(&mut self, key: &str, issue: CachedIssue) {
+        self.issues.insert(key.to_string(), issue);
+    }
+
+    /// Drops entries older than `ttl` so the file does not keep growing.
+    pub fn prune(&mut self, now: u64, ttl: u64) {
+        self.issues.retain(|_, issue| now.saturating_sub(issue.fetched_at) < ttl);
+    }
+}
+
+/// Filters of `log`; a commit matches when one of its issues matches all of them.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    pub issue: Option<String>,
+    pub status: Option<String>,
+    /// Part of the display name, or the exact username or account ID.
+    pub assignee: Option<String>,
+}
+
+impl LogFilter {
+    pub fn is_empty(&self) -> bool {
+        self.issue.is_none() && self.status.is_none() && self.assignee.is_none()
+    }
+
+    pub fn matches(&self, keys: &[String], issues: &HashMap<String, CachedIssue>) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        keys.iter().any(|key| {
+            let issue = issues.get(key);
+            let issue_matches = self.issue.as_ref().is_none_or(|wanted| wanted.eq_ignore_ascii_case(key));
+            let status_matches = self
+                .status
+                .as_ref()
+                .is_none_or(|wanted| issue.is_some_and(|i| i.status.eq_ignore_ascii_case(wanted)));
+            let assignee_matches = self.assignee.as_ref().is_none_or(|wanted| {
+                issue.and_then(|i| i.assignee.as_ref()).is_some_and(|user| assignee_matches(user, wanted))
+            });
+            issue_matches && status_matches && assignee_matches
+        })
+    }
+}
+
+fn assignee_matches(user: &User, wanted: &str) -> bool {
+    user.display_name.to_lowercase().contains(&wanted.to_lowercase())
+        || user.name.as_deref() == Some(wanted)
+        || user.account_id.as_deref() == Some(wanted)
+}
+
+/// A line of the output: the graph drawing and, unless the line only
+/// continues the graph, a commit. With `--graph`, commits hidden by the
+/// filters keep their line with the hash only, so the graph stays intact.
+#[derive(Debug, Clone)]
+pub struct LogLine<'a> {
+    pub graph: String,
+    pub commit: Option<&'a CommitInfo>,
+    pub keys: &'a [String],
+    pub hidden: bool,
+}
+
+/// Lays out the lines with aligned key and status columns; the summary of
+/// the first issue (or the commit subject when there is none) takes the
+/// remaining `width`.
+pub fn format_lines(lines: &[LogLine], issues: &HashMap<String, CachedIssue>, width: usize) -> String {
+    let key_label = |keys: &[String]| match keys {
+        [] => String::new(),
+        [key] => key.clone(),
+        [key, rest @ ..] => format!("{} +{}", key, rest.len()),
+    };
+    let shown = || lines.iter().filter(|l| l.commit.is_some() && !l.hidden);
+    let graph_width = lines.iter().map(|l| display_width(&l.graph)).max().unwrap_or(0);
+    let key_width = shown().map(|l| display_width(&key_label(l.keys))).max().unwrap_or(0);
+    let status_width = shown()
+        .filter_map(|l| l.keys.first().and_then(|k| issues.get(k)))
+        .map(|i| display_width(&i.status))
+        .max()
+        .unwrap_or(0);
+
+    let mut out = Vec::new();
+    for line in lines {
+        let Some(commit) = line.commit else {
+            out.push(line.graph.trim_end().to_string());
+            continue;
+        };
+        let mut text = format!("{}{}", line.graph, " ".repeat(graph_width - display_width(&line.graph)));
+        text.push_str(&commit.short_hash);
+        if line.hidden {
+            out.push(text);
+            continue;
+        }
+
+        let issue = line.keys.first().and_then(|k| issues.get(k));
+        let key = key_label(line.keys);
+        let status = issue.map_or("", |i| i.status.as_str());
+        text.push_str("  ");
+        text.push_str(&key);
+        text.push_str(&" ".repeat(key_width - display_width(&key) + 2));
+        text.push_str(status);
+        text.push_str(&" ".repeat(status_width - display_width(status) + 2));
+
+        let summary = issue.map_or(commit.subject.as_str(), |i| i.summary.as_str());
+        let summary_width = width.saturating_sub(display_width(&text)).max(20);
+        text.push_str(&truncate_at_word(summary, summary_width, "…"));
+        out.push(text.trim_end().to_string());
+    }
+    out.join("\n")
+}
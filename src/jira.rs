@@ -1,24 +1,161 @@
-use crate::AppError;
+use crate::issue::{Comment, Issue, IssueStatus, User, ISSUE_FIELDS};
+use crate::{app_config, AppError};
 use config::Config;
-use reqwest::Client;
+use futures::StreamExt;
+use reqwest::{Client, RequestBuilder, Response};
 use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
 use serde::{Deserialize, Serialize};
-use regex::Regex;
-use lazy_static::lazy_static;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
 
 pub struct JiraClient {
-    client: Client,
+    // 登录后会替换为携带 session cookie 的 client
+    client: RwLock<Client>,
     config: Config,
 }
 
 #[derive(Deserialize)]
-struct JiraIssue {
-    fields: JiraFields,
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    issues: Vec<Issue>,
+    #[serde(default)]
+    total: usize,
 }
 
 #[derive(Deserialize)]
-struct JiraFields {
-    summary: String,
+struct TransitionsResponse {
+    transitions: Vec<Transition>,
+}
+
+/// A workflow transition available for an issue.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Transition {
+    pub id: String,
+    pub name: String,
+    pub to: IssueStatus,
+    /// Fields on the transition screen, keyed by field ID.
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldMeta>,
+}
+
+/// A field on a transition or create screen, such as `resolution` or `comment`.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMeta {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub has_default_value: bool,
+    #[serde(default)]
+    pub allowed_values: Vec<AllowedValue>,
+    pub schema: Option<FieldSchema>,
+}
+
+/// Type of a field value, e.g. `string`, `priority` or `array` of `component`.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct FieldSchema {
+    #[serde(rename = "type", default)]
+    pub field_type: String,
+    /// Element type of `array` fields.
+    pub items: Option<String>,
+}
+
+/// An issue type that can be created in a project.
+#[derive(Deserialize, Debug, Clone)]
+pub struct IssueTypeMeta {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub subtask: bool,
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    values: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateFieldMeta {
+    field_id: String,
+    #[serde(flatten)]
+    meta: FieldMeta,
+}
+
+/// Response of the `createmeta` endpoint used before JIRA 9.
+#[derive(Deserialize)]
+struct LegacyCreateMeta {
+    projects: Vec<LegacyProjectMeta>,
+}
+
+#[derive(Deserialize)]
+struct LegacyProjectMeta {
+    issuetypes: Vec<LegacyIssueTypeMeta>,
+}
+
+#[derive(Deserialize)]
+struct LegacyIssueTypeMeta {
+    #[serde(flatten)]
+    issue_type: IssueTypeMeta,
+    #[serde(default)]
+    fields: BTreeMap<String, FieldMeta>,
+}
+
+#[derive(Deserialize)]
+struct CreatedIssue {
+    key: String,
+}
+
+/// One of the allowed values of a field; resolutions have an ID and a
+/// name, select lists an ID and a value.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct AllowedValue {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub value: Option<String>,
+}
+
+/// A link from an issue to a page outside JIRA. Links with the same
+/// `global_id` are updated instead of added again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteLink {
+    pub global_id: String,
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+}
+
+/// A version of a project, used as fix version.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectVersion {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub released: bool,
+    pub release_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProjectInfo {
+    id: String,
+}
+
+/// Page size used when paginating searches.
+const SEARCH_PAGE_SIZE: usize = 100;
+
+/// How many issue keys are put in one `key in (...)` query.
+const KEYS_BATCH: usize = 100;
+
+/// How many of those queries run at the same time.
+const KEYS_CONCURRENCY: usize = 4;
+
+#[derive(Deserialize)]
+struct CommentsResponse {
+    comments: Vec<Comment>,
 }
 
 #[derive(Serialize)]
@@ -41,19 +178,23 @@ struct SessionInfo {
 impl JiraClient {
     pub fn new(config: &Config) -> Result<Self, AppError> {
         Ok(JiraClient {
-            client: Client::new(),
+            client: RwLock::new(Client::new()),
             config: config.clone(),
         })
     }
 
-    async fn login(&mut self) -> Result<(), AppError> {
+    fn client(&self) -> Client {
+        self.client.read().expect("JIRA client lock poisoned").clone()
+    }
+
+    async fn login(&self) -> Result<(), AppError> {
         let login_url = format!("{}/rest/auth/1/session", self.config.get_string("jira_url")?);
         let credentials = LoginCredentials {
             username: self.config.get_string("username")?,
-            password: self.config.get_string("password")?,
+            password: app_config::get_password(&self.config)?,
         };
 
-        let response = self.client
+        let response = self.client()
             .post(&login_url)
             .json(&credentials)
             .send()
@@ -69,40 +210,542 @@ impl JiraClient {
         let mut headers = HeaderMap::new();
         headers.insert(COOKIE, HeaderValue::from_str(&session_cookie).map_err(|e| AppError::JiraApi(e.to_string()))?);
 
-        self.client = Client::builder()
+        let client = Client::builder()
             .default_headers(headers)
             .build()
             .map_err(|e| AppError::JiraApi(e.to_string()))?;
+        *self.client.write().expect("JIRA client lock poisoned") = client;
 
         Ok(())
     }
 
-    pub async fn get_issue_title(&mut self, jira_id: &str) -> Result<String, AppError> {
-        let jira_api_url = format!("{}/rest/api/2/issue/{}", self.config.get_string("jira_url")?, jira_id);
-
-        let mut response = self.client.get(&jira_api_url).send().await?;
+    async fn send<F>(&self, build: F) -> Result<Response, AppError>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let mut response = build(&self.client()).send().await?;
 
         if response.status() == reqwest::StatusCode::UNAUTHORIZED || response.status() == reqwest::StatusCode::FORBIDDEN {
             // Session might be expired, try to login again
             self.login().await?;
             // Retry the request
-            response = self.client.get(&jira_api_url).send().await?;
+            response = build(&self.client()).send().await?;
+        }
+
+        Ok(response)
+    }
+
+    /// REST API version from `api_version`: 2 (wiki markup, the default) or
+    /// 3 (Atlassian Document Format, JIRA Cloud).
+    pub fn api_version(&self) -> u8 {
+        match self.config.get_string("api_version").as_deref() {
+            Ok("3") => 3,
+            _ => 2,
+        }
+    }
+
+    fn api_url(&self, path: &str) -> Result<String, AppError> {
+        Ok(format!(
+            "{}/rest/api/{}/{}",
+            self.config.get_string("jira_url")?.trim_end_matches('/'),
+            self.api_version(),
+            path
+        ))
+    }
+
+    /// Plain text as a rich text value: the text itself for API v2, an
+    /// Atlassian Document Format document with one paragraph per line and
+    /// linked URLs for v3.
+    pub fn text_body(&self, text: &str) -> Value {
+        if self.api_version() < 3 {
+            return Value::String(text.to_string());
+        }
+
+        let paragraphs: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::json!({ "type": "paragraph", "content": adf_inline(line) }))
+            .collect();
+        serde_json::json!({ "type": "doc", "version": 1, "content": paragraphs })
+    }
+
+    /// The `fields=` list for [`Issue`], including the epic link custom
+    /// field when `epic_link_field` is configured.
+    fn issue_fields(&self, with_description: bool) -> Vec<String> {
+        let mut fields: Vec<String> = ISSUE_FIELDS
+            .iter()
+            .filter(|field| with_description || **field != "description")
+            .map(|field| field.to_string())
+            .collect();
+        if let Ok(epic_field) = self.config.get_string("epic_link_field") {
+            fields.push(epic_field);
         }
+        fields
+    }
+
+    /// Fetches the given fields of an issue. Returns `None` when the issue does not exist.
+    async fn fetch_issue(&self, jira_id: &str, fields: &str) -> Result<Option<Issue>, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}", jira_id))?;
 
+        let response = self.send(|client| client.get(&jira_api_url).query(&[("fields", fields)])).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
         if !response.status().is_success() {
             return Err(AppError::JiraApi(format!("Failed to get JIRA issue: {}", response.status())));
         }
 
-        let issue: JiraIssue = response.json().await?;
-        Ok(issue.fields.summary)
+        Ok(Some(self.with_epic(response.json().await?)))
+    }
+
+    /// Reads the epic from the field configured as `epic_link_field`.
+    fn with_epic(&self, mut issue: Issue) -> Issue {
+        if let Ok(epic_field) = self.config.get_string("epic_link_field") {
+            issue.resolve_epic(&epic_field);
+        }
+        issue
+    }
+
+    pub async fn get_issue(&self, jira_id: &str) -> Result<Issue, AppError> {
+        self.fetch_issue(jira_id, &self.issue_fields(true).join(","))
+            .await?
+            .ok_or_else(|| AppError::JiraApi(format!("JIRA issue {} not found", jira_id)))
+    }
+
+    pub async fn get_issue_title(&self, jira_id: &str) -> Result<String, AppError> {
+        self.fetch_issue(jira_id, "summary")
+            .await?
+            .map(|issue| issue.summary)
+            .ok_or_else(|| AppError::JiraApi(format!("JIRA issue {} not found", jira_id)))
+    }
+
+    /// Fetches only the status of an issue. Returns `None` when the issue does not exist.
+    pub async fn get_issue_status(&self, jira_id: &str) -> Result<Option<IssueStatus>, AppError> {
+        Ok(self.fetch_issue(jira_id, "status").await?.and_then(|issue| issue.status))
+    }
+
+    /// Returns the latest `count` comments of an issue, oldest first.
+    pub async fn get_comments(&self, jira_id: &str, count: u32) -> Result<Vec<Comment>, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/comment", jira_id))?;
+        let count = count.to_string();
+        let query = [("orderBy", "-created"), ("maxResults", count.as_str())];
+
+        let response = self.send(|client| client.get(&jira_api_url).query(&query)).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA comments: {}", response.status())));
+        }
+
+        let result: CommentsResponse = response.json().await?;
+        let mut comments = result.comments;
+        comments.reverse();
+        Ok(comments)
+    }
+
+    /// Runs a JQL query, following `startAt`/`maxResults`/`total` until all
+    /// matching issues, or `limit` of them, have been fetched. Descriptions
+    /// are not requested to keep the pages small.
+    pub async fn search_issues(&self, jql: &str, limit: Option<usize>) -> Result<Vec<Issue>, AppError> {
+        self.search(jql, limit, &[], "strict").await
+    }
+
+    /// Fetches the issues with the given keys in concurrent batches, also
+    /// requesting the extra custom fields. Keys that do not exist or are not
+    /// visible are left out instead of failing the query.
+    pub async fn get_issues_by_keys(&self, keys: &[String], extra_fields: &[String]) -> Result<Vec<Issue>, AppError> {
+        let pages: Vec<Result<Vec<Issue>, AppError>> = futures::stream::iter(keys.chunks(KEYS_BATCH))
+            .map(|chunk| async move {
+                let jql = crate::list::keys_jql(chunk);
+                log::debug!("JQL: {}", jql);
+                self.search(&jql, None, extra_fields, "warn").await
+            })
+            .buffered(KEYS_CONCURRENCY)
+            .collect()
+            .await;
+        let mut issues = Vec::new();
+        for page in pages {
+            issues.extend(page?);
+        }
+        Ok(issues)
+    }
+
+    async fn search(&self, jql: &str, limit: Option<usize>, extra_fields: &[String], validate: &str) -> Result<Vec<Issue>, AppError> {
+        let jira_api_url = self.api_url("search")?;
+        let mut fields = self.issue_fields(false);
+        fields.extend(extra_fields.iter().cloned());
+        let fields = fields.join(",");
+        let mut issues: Vec<Issue> = Vec::new();
+
+        loop {
+            let page_size = limit.map_or(SEARCH_PAGE_SIZE, |limit| (limit - issues.len()).min(SEARCH_PAGE_SIZE));
+            let start_at = issues.len().to_string();
+            let max_results = page_size.to_string();
+            let query = [
+                ("jql", jql),
+                ("fields", fields.as_str()),
+                ("startAt", start_at.as_str()),
+                ("maxResults", max_results.as_str()),
+                ("validateQuery", validate),
+            ];
+
+            let response = self.send(|client| client.get(&jira_api_url).query(&query)).await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(AppError::JiraApi(format!("Failed to search JIRA issues: {} {}", status, body)));
+            }
+
+            let page: SearchResponse = response.json().await?;
+            let received = page.issues.len();
+            issues.extend(page.issues.into_iter().map(|issue| self.with_epic(issue)));
+
+            let reached_limit = limit.is_some_and(|limit| issues.len() >= limit);
+            if received == 0 || issues.len() >= page.total || reached_limit {
+                break;
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Returns the user the client is logged in as.
+    pub async fn get_myself(&self) -> Result<User, AppError> {
+        let jira_api_url = self.api_url("myself")?;
+
+        let response = self.send(|client| client.get(&jira_api_url)).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get current JIRA user: {}", response.status())));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn assign_issue(&self, jira_id: &str, user: &User) -> Result<(), AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/assignee", jira_id))?;
+        // JIRA Cloud 使用 accountId，Server 使用用户名
+        let body = match (&user.account_id, &user.name) {
+            (Some(account_id), _) => serde_json::json!({ "accountId": account_id }),
+            (None, Some(name)) => serde_json::json!({ "name": name }),
+            (None, None) => return Err(AppError::JiraApi("JIRA user has neither account ID nor name".to_string())),
+        };
+
+        let response = self.send(|client| client.put(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to assign JIRA issue: {}", response.status())));
+        }
+
+        Ok(())
+    }
+
+    /// Adds a plain text comment to the issue.
+    pub async fn add_comment(&self, jira_id: &str, text: &str) -> Result<(), AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/comment", jira_id))?;
+        let body = serde_json::json!({ "body": self.text_body(text) });
+
+        let response = self.send(|client| client.post(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to add JIRA comment: {} {}", status, body)));
+        }
+
+        Ok(())
+    }
+
+    /// Logs work on the issue. `time_spent` uses JIRA's duration syntax and
+    /// `started` the `2024-03-01T18:30:00.000+0800` format.
+    pub async fn add_worklog(&self, jira_id: &str, time_spent: &str, started: &str, comment: &str) -> Result<(), AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/worklog", jira_id))?;
+        let mut body = serde_json::json!({ "timeSpent": time_spent, "started": started });
+        if !comment.is_empty() {
+            body["comment"] = self.text_body(comment);
+        }
+
+        let response = self.send(|client| client.post(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to log work on JIRA issue: {} {}", status, body)));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the issue types that can be created in the project.
+    pub async fn get_create_issue_types(&self, project: &str) -> Result<Vec<IssueTypeMeta>, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/createmeta/{}/issuetypes", project))?;
+
+        let response = self.send(|client| client.get(&jira_api_url)).await?;
+
+        // JIRA 9 之前没有这个接口
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            let legacy = self.get_legacy_create_meta(project).await?;
+            return Ok(legacy.into_iter().map(|t| t.issue_type).collect());
+        }
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA issue types: {}", response.status())));
+        }
+
+        let page: Page<IssueTypeMeta> = response.json().await?;
+        Ok(page.values)
+    }
+
+    /// Returns the fields of the create screen for the issue type, keyed by field ID.
+    pub async fn get_create_fields(&self, project: &str, issue_type: &IssueTypeMeta) -> Result<BTreeMap<String, FieldMeta>, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/createmeta/{}/issuetypes/{}", project, issue_type.id))?;
+        let query = [("maxResults", "200")];
+
+        let response = self.send(|client| client.get(&jira_api_url).query(&query)).await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            let legacy = self.get_legacy_create_meta(project).await?;
+            return Ok(legacy
+                .into_iter()
+                .find(|t| t.issue_type.id == issue_type.id)
+                .map(|t| t.fields)
+                .unwrap_or_default());
+        }
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA create fields: {}", response.status())));
+        }
+
+        let page: Page<CreateFieldMeta> = response.json().await?;
+        Ok(page.values.into_iter().map(|f| (f.field_id, f.meta)).collect())
+    }
+
+    async fn get_legacy_create_meta(&self, project: &str) -> Result<Vec<LegacyIssueTypeMeta>, AppError> {
+        let jira_api_url = self.api_url("issue/createmeta")?;
+        let query = [("projectKeys", project), ("expand", "projects.issuetypes.fields")];
+
+        let response = self.send(|client| client.get(&jira_api_url).query(&query)).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA create metadata: {}", response.status())));
+        }
+
+        let meta: LegacyCreateMeta = response.json().await?;
+        meta.projects
+            .into_iter()
+            .next()
+            .map(|p| p.issuetypes)
+            .ok_or_else(|| AppError::JiraApi(format!("JIRA project {} not found or not writable", project)))
+    }
+
+    /// Creates an issue and returns its key.
+    pub async fn create_issue(&self, fields: &serde_json::Map<String, Value>) -> Result<String, AppError> {
+        let jira_api_url = self.api_url("issue")?;
+        let body = serde_json::json!({ "fields": fields });
+
+        let response = self.send(|client| client.post(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to create JIRA issue: {} {}", status, body)));
+        }
+
+        let created: CreatedIssue = response.json().await?;
+        Ok(created.key)
+    }
+
+    /// Returns all versions of the project.
+    pub async fn get_project_versions(&self, project: &str) -> Result<Vec<ProjectVersion>, AppError> {
+        let jira_api_url = self.api_url(&format!("project/{}/versions", project))?;
+
+        let response = self.send(|client| client.get(&jira_api_url)).await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get versions of project {}: {}", project, response.status())));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Creates an unreleased version in the project. `release_date` uses the
+    /// `2024-03-01` format.
+    pub async fn create_version(&self, project: &str, name: &str, release_date: &str) -> Result<ProjectVersion, AppError> {
+        let project_url = self.api_url(&format!("project/{}", project))?;
+        let response = self.send(|client| client.get(&project_url)).await?;
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA project {}: {}", project, response.status())));
+        }
+        let info: ProjectInfo = response.json().await?;
+        let project_id: u64 = info
+            .id
+            .parse()
+            .map_err(|_| AppError::JiraApi(format!("Unexpected id '{}' for JIRA project {}", info.id, project)))?;
+
+        // "project" 按 key 指定的方式已弃用，使用 projectId
+        let jira_api_url = self.api_url("version")?;
+        let body = serde_json::json!({
+            "projectId": project_id,
+            "name": name,
+            "releaseDate": release_date,
+            "released": false,
+        });
+
+        let response = self.send(|client| client.post(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to create JIRA version: {} {}", status, body)));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Marks the version as released on the given date.
+    pub async fn release_version(&self, version_id: &str, release_date: &str) -> Result<(), AppError> {
+        let jira_api_url = self.api_url(&format!("version/{}", version_id))?;
+        let body = serde_json::json!({ "released": true, "releaseDate": release_date });
+
+        let response = self.send(|client| client.put(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to release JIRA version: {} {}", status, body)));
+        }
+
+        Ok(())
+    }
+
+    /// Adds a fix version to the issue, keeping its other fix versions.
+    pub async fn add_fix_version(&self, jira_id: &str, version: &str) -> Result<(), AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}", jira_id))?;
+        let body = serde_json::json!({ "update": { "fixVersions": [{ "add": { "name": version } }] } });
+
+        let response = self.send(|client| client.put(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to set fix version of {}: {} {}", jira_id, status, body)));
+        }
+
+        Ok(())
+    }
+
+    /// Adds a remote link to the issue, or updates the one with the same
+    /// global ID. Returns whether a new link was created.
+    pub async fn add_remote_link(&self, jira_id: &str, link: &RemoteLink) -> Result<bool, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/remotelink", jira_id))?;
+        let body = serde_json::json!({
+            "globalId": link.global_id,
+            "application": { "type": "git", "name": "Git" },
+            "object": { "url": link.url, "title": link.title, "summary": link.summary },
+        });
+
+        let response = self.send(|client| client.post(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to add JIRA remote link: {} {}", status, body)));
+        }
+
+        Ok(response.status() == reqwest::StatusCode::CREATED)
+    }
+
+    /// Returns whether the issue has a remote link with the global ID.
+    pub async fn has_remote_link(&self, jira_id: &str, global_id: &str) -> Result<bool, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/remotelink", jira_id))?;
+
+        let response = self
+            .send(|client| client.get(&jira_api_url).query(&[("globalId", global_id)]))
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA remote links: {}", response.status())));
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the transitions currently available for the issue, with the
+    /// fields of their screens.
+    pub async fn get_transitions(&self, jira_id: &str) -> Result<Vec<Transition>, AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/transitions", jira_id))?;
+
+        let response = self
+            .send(|client| client.get(&jira_api_url).query(&[("expand", "transitions.fields")]))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::JiraApi(format!("Failed to get JIRA transitions: {}", response.status())));
+        }
+
+        let result: TransitionsResponse = response.json().await?;
+        Ok(result.transitions)
+    }
+
+    /// Performs a transition, setting the screen fields and adding the
+    /// comment when given.
+    pub async fn transition_issue(
+        &self,
+        jira_id: &str,
+        transition_id: &str,
+        fields: &serde_json::Map<String, Value>,
+        comment: Option<&str>,
+    ) -> Result<(), AppError> {
+        let jira_api_url = self.api_url(&format!("issue/{}/transitions", jira_id))?;
+        let mut body = serde_json::json!({ "transition": { "id": transition_id } });
+        if !fields.is_empty() {
+            body["fields"] = Value::Object(fields.clone());
+        }
+        if let Some(comment) = comment {
+            body["update"] = serde_json::json!({ "comment": [{ "add": { "body": self.text_body(comment) } }] });
+        }
+
+        let response = self.send(|client| client.post(&jira_api_url).json(&body)).await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::JiraApi(format!("Failed to transition JIRA issue: {} {}", status, body)));
+        }
+
+        Ok(())
     }
 }
 
-pub fn extract_jira_id(branch_name: &str, jira_id_prefix: &str) -> Option<String> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(?i)([A-Z]+-\d+)").unwrap();
+/// Text nodes of an ADF paragraph, with `http(s)://` words as links.
+fn adf_inline(line: &str) -> Vec<Value> {
+    let mut nodes = Vec::new();
+    let mut text = String::new();
+    for (i, word) in line.split(' ').enumerate() {
+        if i > 0 {
+            text.push(' ');
+        }
+        if word.starts_with("http://") || word.starts_with("https://") {
+            if !text.is_empty() {
+                nodes.push(serde_json::json!({ "type": "text", "text": std::mem::take(&mut text) }));
+            }
+            nodes.push(serde_json::json!({
+                "type": "text",
+                "text": word,
+                "marks": [{ "type": "link", "attrs": { "href": word } }]
+            }));
+        } else {
+            text.push_str(word);
+        }
+    }
+    if !text.is_empty() {
+        nodes.push(serde_json::json!({ "type": "text", "text": text }));
     }
-    RE.find(branch_name)
-        .map(|m| m.as_str().to_uppercase())
-        .filter(|id| id.starts_with(jira_id_prefix))
-}
\ No newline at end of file
+    nodes
+}
+
+/// Returns the browser link of an issue.
+pub fn browse_url(jira_url: &str, key: &str) -> String {
+    format!("{}/browse/{}", jira_url.trim_end_matches('/'), key)
+}
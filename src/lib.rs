@@ -1,16 +1,63 @@
 use config::Config;
+use futures::StreamExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub mod error;
+pub mod issue;
 pub mod app_config;
 pub mod git;
 pub mod jira;
 pub mod input;
+pub mod hook;
+pub mod message;
+pub mod issue_key;
+pub mod sanitize;
+pub mod render;
+pub mod list;
+pub mod branch;
+pub mod slug;
+pub mod workflow;
+pub mod remote;
+pub mod commit_comment;
+pub mod worklog;
+pub mod smart_commit;
+pub mod create;
+pub mod remote_link;
+pub mod history;
+pub mod release_notes;
+pub mod changelog;
+pub mod release;
+pub mod next_version;
+pub mod audit;
+pub mod issue_log;
 
 pub use error::AppError;
 pub use crate::app_config::AppConfig;
+pub use crate::issue::Issue;
 pub use crate::jira::JiraClient;
 pub use crate::git::GitOperations;
 
+/// How many recent commits are searched for a JIRA key when the branch has none.
+const RECENT_COMMITS: usize = 10;
+
+/// How many comments of an issue are searched for a commit before
+/// `sync-history` comments it.
+const HISTORY_COMMENT_SCAN: u32 = 1000;
+
+const DEFAULT_PICKER_JQL: &str = "assignee = currentUser() AND statusCategory != Done ORDER BY updated DESC";
+
+/// Options for the default commit flow.
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Issue key or browse URL given with `--issue`.
+    pub issue: Option<String>,
+    /// Time spent given with `--time`, in JIRA duration syntax.
+    pub time: Option<String>,
+    /// Worklog comment given with `--time-comment`; the commit subject by default.
+    pub time_comment: Option<String>,
+}
+
 pub struct App {
     config: Config,
     jira_client: jira::JiraClient,
@@ -23,7 +70,7 @@ impl App {
         let config = app_config::load_config()?;
         
         // 基本验证
-        if config.get_string("jira_url").is_err() || config.get_string("username").is_err() {
+        if !app_config::has_password(&config) {
             return Err(AppError::ConfigMissing);
         }
 
@@ -37,21 +84,70 @@ impl App {
         })
     }
 
-    pub async fn run(&mut self) -> Result<(), AppError> {
+    pub async fn run(&mut self, options: &CommitOptions) -> Result<(), AppError> {
         let branch_name = self.git_ops.get_current_branch().await?;
-        let jira_id = self.extract_jira_id(&branch_name)?;
+        let mut issues = self.resolve_issues(&branch_name, options.issue.as_deref()).await?;
+        let sanitizer = self.sanitizer()?;
+        for issue in &mut issues {
+            issue.summary = sanitizer.apply(&issue.summary);
+        }
 
-        let jira_title = self.jira_client.get_issue_title(&jira_id).await?;
+        for issue in &issues {
+            println!("JIRA ID: {}", issue.key);
+            println!("JIRA Title: {}", issue.summary);
+            let details: Vec<&str> = [issue.issue_type.as_deref().unwrap_or_default(), issue.status_name(), issue.assignee_name()]
+                .into_iter()
+                .filter(|d| !d.is_empty())
+                .collect();
+            if !details.is_empty() {
+                println!("JIRA Details: {}", details.join(" | "));
+            }
+        }
 
-        println!("JIRA ID: {}", jira_id);
-        println!("JIRA Title: {}", jira_title);
+        let smart_settings: smart_commit::SmartCommitSettings = app_config::get_section(&self.config, "smart_commits")?;
+        let mut additional_message = input::prompt_for_commit_message();
+        let smart_commit = if smart_settings.enabled {
+            smart_commit::parse(&additional_message)?
+        } else {
+            smart_commit::SmartCommit::default()
+        };
 
-        let additional_message = input::prompt_for_commit_message();
-        let commit_message = format!("[{}] {} {}", jira_id, jira_title, additional_message);
+        // 先校验时长，避免提交后才发现格式错误；#time 命令已记录工时时不再重复记录
+        let has_smart_time = smart_commit
+            .commands
+            .iter()
+            .any(|command| matches!(command, smart_commit::SmartCommand::Time { .. }));
+        let time_spent = if has_smart_time {
+            if options.time.is_some() {
+                println!("Ignoring --time, the #time command in the message logs the work.");
+            }
+            None
+        } else {
+            match &options.time {
+                Some(time) => Some(worklog::normalize_duration(time)?),
+                None => self.prompt_time_spent()?,
+            }
+        };
+        if smart_settings.strip && !smart_commit.commands.is_empty() {
+            additional_message = smart_commit.message.clone();
+        }
+        let commit_message = self.build_commit_message(&issues, &additional_message).await?;
 
         if input::confirm_commit(&commit_message) {
             self.git_ops.commit(&commit_message).await?;
             println!("Commit successful!");
+            if let Err(e) = self.after_commit(&branch_name, &issues).await {
+                println!("Warning: could not run the first-commit transition: {}", e);
+            }
+            if let Err(e) = self.comment_commit(&branch_name, &issues).await {
+                println!("Warning: could not comment on JIRA: {}", e);
+            }
+            if let Some(time_spent) = time_spent {
+                if let Err(e) = self.log_work(&issues, &time_spent, options.time_comment.as_deref()).await {
+                    println!("Warning: could not log work on JIRA: {}", e);
+                }
+            }
+            self.run_smart_commands(&issues, &smart_commit.commands).await;
         } else {
             println!("Commit cancelled.");
         }
@@ -59,9 +155,1289 @@ impl App {
         Ok(())
     }
 
-    fn extract_jira_id(&self, branch_name: &str) -> Result<String, AppError> {
-        let jira_id_prefix = self.config.get_string("jira_id_prefix")?;
-        jira::extract_jira_id(branch_name, &jira_id_prefix)
-            .ok_or_else(|| AppError::Other("JIRA ID not found in branch name".to_string()))
+    /// Prints issues in the terminal: the given key, or the keys in the
+    /// current branch name.
+    pub async fn show(&self, reference: Option<&str>, comment_count: u32) -> Result<(), AppError> {
+        let keys = match reference {
+            Some(reference) => vec![issue_key::parse_issue_reference(reference)
+                .ok_or_else(|| AppError::Other(format!("Not a JIRA issue key or URL: {}", reference)))?],
+            None => {
+                let branch_name = self.git_ops.get_current_branch().await?;
+                let keys = issue_key::KeyExtractor::from_config(&self.config)?.extract(&branch_name);
+                if keys.is_empty() {
+                    return Err(AppError::Other("JIRA ID not found in branch name".to_string()));
+                }
+                keys
+            }
+        };
+
+        let jira_url = self.config.get_string("jira_url")?;
+        let renderer = render::Renderer::detect();
+        for (i, key) in keys.iter().enumerate() {
+            let (issue, comments) = tokio::try_join!(
+                self.jira_client.get_issue(key),
+                self.jira_client.get_comments(key, comment_count),
+            )?;
+            if i > 0 {
+                println!();
+            }
+            println!("{}", renderer.issue(&issue, &jira::browse_url(&jira_url, key), &comments));
+        }
+        Ok(())
+    }
+
+    /// Starts work on an issue: creates and checks out its branch, then
+    /// optionally assigns the issue to the current user and moves it to the
+    /// configured in-progress status.
+    pub async fn start(&self, reference: &str, base_override: Option<&str>) -> Result<(), AppError> {
+        let key = issue_key::parse_issue_reference(reference)
+            .ok_or_else(|| AppError::Other(format!("Not a JIRA issue key or URL: {}", reference)))?;
+        let mut settings: branch::StartSettings = app_config::get_section(&self.config, "start")?;
+        if let Some(base) = base_override {
+            settings.base_branch = Some(base.to_string());
+        }
+
+        let issue = self.jira_client.get_issue(&key).await?;
+        println!("JIRA ID: {}", issue.key);
+        println!("JIRA Title: {}", issue.summary);
+
+        let branch_name = branch::branch_name(&issue, &settings);
+        if self.git_ops.branch_exists(&branch_name).await? {
+            println!("Branch {} already exists, checking it out.", branch_name);
+            self.git_ops.checkout(&branch_name).await?;
+        } else {
+            let start_point = match &settings.base_branch {
+                Some(base) if settings.fetch => {
+                    self.git_ops.fetch(&settings.remote, base).await?;
+                    Some(format!("{}/{}", settings.remote, base))
+                }
+                Some(base) => Some(base.clone()),
+                None => None,
+            };
+            self.git_ops.checkout_new_branch(&branch_name, start_point.as_deref()).await?;
+            println!("Created branch {}", branch_name);
+        }
+
+        if settings.assign {
+            let myself = self.jira_client.get_myself().await?;
+            self.jira_client.assign_issue(&key, &myself).await?;
+            println!("Assigned {} to {}", key, myself.display_name);
+        }
+
+        if let Some(status) = &settings.in_progress_status {
+            self.move_to_status(&key, issue.status_name(), status).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves an issue through its workflow. Without a status the available
+    /// transitions are listed to pick from; required screen fields such as
+    /// the resolution are prompted for.
+    pub async fn transition(&self, reference: Option<&str>, status: Option<&str>, comment: Option<&str>) -> Result<(), AppError> {
+        // `transition Done` 只给了状态，issue 取自当前分支
+        let (reference, status) = match (reference, status) {
+            (Some(reference), None) if issue_key::parse_issue_reference(reference).is_none() => (None, Some(reference)),
+            other => other,
+        };
+
+        let key = match reference {
+            Some(reference) => issue_key::parse_issue_reference(reference)
+                .ok_or_else(|| AppError::Other(format!("Not a JIRA issue key or URL: {}", reference)))?,
+            None => {
+                let branch_name = self.git_ops.get_current_branch().await?;
+                let mut keys = self.branch_issue_keys(&branch_name).await?;
+                match keys.len() {
+                    0 => return Err(AppError::Other("JIRA ID not found in branch name".to_string())),
+                    1 => keys.remove(0),
+                    _ => keys.remove(input::select_item("Select an issue", &keys, "1")?),
+                }
+            }
+        };
+
+        let transitions = self.jira_client.get_transitions(&key).await?;
+        if transitions.is_empty() {
+            return Err(AppError::JiraApi(format!("No transitions are available for {}", key)));
+        }
+        let transition = match status {
+            Some(status) => branch::find_transition(&transitions, status).ok_or_else(|| {
+                let available: Vec<String> = transitions.iter().map(workflow::transition_label).collect();
+                AppError::Other(format!("No transition to '{}' for {}. Available: {}", status, key, available.join(", ")))
+            })?,
+            None => {
+                println!("Transitions for {}:", key);
+                let labels: Vec<String> = transitions.iter().map(workflow::transition_label).collect();
+                &transitions[input::select_item("Select a transition", &labels, "1")?]
+            }
+        };
+
+        let mut fields = serde_json::Map::new();
+        for (id, field) in workflow::required_fields(transition) {
+            fields.insert(id.to_string(), prompt_field(id, field)?);
+        }
+
+        let comment = match comment {
+            Some(comment) => Some(comment.to_string()),
+            None if workflow::has_comment_field(transition) => {
+                let required = transition.fields.get(workflow::COMMENT_FIELD).is_some_and(|f| f.required);
+                if required {
+                    Some(prompt_required("Comment")?)
+                } else {
+                    Some(input::prompt_for_input("Comment (optional)", None)?).filter(|c| !c.is_empty())
+                }
+            }
+            None => None,
+        };
+
+        self.jira_client.transition_issue(&key, &transition.id, &fields, comment.as_deref()).await?;
+        println!("Moved {} to {}", key, transition.to.name);
+        Ok(())
+    }
+
+    /// Creates an issue, prompting for whatever the create screen requires
+    /// and was not given, and optionally starts work on it.
+    pub async fn create(&self, mut issue: create::NewIssue, issue_type: Option<&str>, edit_description: bool, start: bool) -> Result<(), AppError> {
+        let settings: create::CreateSettings = app_config::get_section(&self.config, "create")?;
+        if issue.project.is_empty() {
+            issue.project = self.default_project(&settings)?;
+        }
+        if issue.summary.trim().is_empty() {
+            issue.summary = prompt_required("Summary")?;
+        }
+
+        let types = self.jira_client.get_create_issue_types(&issue.project).await?;
+        let issue_type = match issue_type.map(str::to_string).or_else(|| settings.issue_type.clone()) {
+            Some(name) => create::find_issue_type(&types, &name)?,
+            None => {
+                // 指定了父 issue 时只列出子任务类型
+                let candidates: Vec<&jira::IssueTypeMeta> = types.iter().filter(|t| t.subtask == issue.parent.is_some()).collect();
+                if candidates.is_empty() {
+                    return Err(AppError::Other(format!("No suitable issue types in {}", issue.project)));
+                }
+                let names: Vec<String> = candidates.iter().map(|t| t.name.clone()).collect();
+                candidates[input::select_item("Select an issue type", &names, "1")?]
+            }
+        };
+
+        if edit_description && issue.description.is_none() {
+            issue.description = Some(input::edit_text("")?).filter(|d| !d.is_empty());
+        }
+        let description = issue.description.as_deref().map(|d| self.jira_client.text_body(d));
+
+        let meta = self.jira_client.get_create_fields(&issue.project, issue_type).await?;
+        let mut fields = create::build_fields(&issue, issue_type, &meta, description)?;
+        for (id, field) in create::missing_fields(&meta, &fields) {
+            fields.insert(id.to_string(), prompt_field(id, field)?);
+        }
+
+        let key = self.jira_client.create_issue(&fields).await?;
+        let jira_url = self.config.get_string("jira_url")?;
+        println!("Created {}", key);
+        println!("{}", jira::browse_url(&jira_url, &key));
+
+        if start || settings.start {
+            self.start(&key, None).await?;
+        }
+        Ok(())
+    }
+
+    /// Project for new issues: `create.project`, the default project of key
+    /// extraction or `jira_id_prefix`, otherwise asked for.
+    fn default_project(&self, settings: &create::CreateSettings) -> Result<String, AppError> {
+        let key_settings: issue_key::KeyExtractionSettings = app_config::get_section(&self.config, "key_extraction")?;
+        let configured = settings
+            .project
+            .clone()
+            .or(key_settings.default_project)
+            .or_else(|| self.config.get_string("jira_id_prefix").ok())
+            .filter(|p| !p.is_empty());
+        match configured {
+            Some(project) => Ok(project),
+            None => prompt_required("Project key"),
+        }
+    }
+
+    /// Runs `git push` with the arguments, then moves the issues of the
+    /// current branch to the status configured in `auto_transition.push`
+    /// and links the pushed commits when `remote_link.enabled` is set.
+    pub async fn push(&self, args: &[String]) -> Result<(), AppError> {
+        let branch_name = self.git_ops.get_current_branch().await?;
+        let link_settings: remote_link::RemoteLinkSettings = app_config::get_section(&self.config, "remote_link")?;
+        // 推送之后就无法区分哪些提交是这次推送的
+        let pushed = if link_settings.enabled && branch_name != "HEAD" {
+            match self.git_ops.unpushed_commits(&branch_name).await {
+                Ok(commits) => commits,
+                Err(e) => {
+                    println!("Warning: could not list the commits to push, they will not be linked: {}", e);
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        self.git_ops.push(args).await?;
+
+        let keys = self.branch_issue_keys(&branch_name).await?;
+        let settings: workflow::AutoTransitionSettings = app_config::get_section(&self.config, "auto_transition")?;
+        if let Some(status) = &settings.push {
+            for key in &keys {
+                self.auto_transition(key, status).await;
+            }
+        }
+
+        if link_settings.enabled {
+            if let Err(e) = self.link_pushed(&link_settings, &branch_name, &keys, &pushed).await {
+                println!("Warning: could not add JIRA remote links: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Links (or comments) every commit in the range on the issues its message
+    /// refers to. Synced commits are recorded in a checkpoint file, so an
+    /// interrupted run resumes where it stopped; remote links are idempotent
+    /// and comments are skipped when the issue already mentions the commit.
+    pub async fn sync_history(
+        &self,
+        range: &str,
+        mode: history::SyncMode,
+        dry_run: bool,
+        concurrency: usize,
+        checkpoint_path: Option<PathBuf>,
+    ) -> Result<(), AppError> {
+        let concurrency = concurrency.max(1);
+        let commits = self.git_ops.log(&["--reverse", range]).await?;
+        let extractor = issue_key::KeyExtractor::from_config(&self.config)?;
+        let mut summary = history::SyncSummary {
+            dry_run,
+            commits_scanned: commits.len(),
+            ..Default::default()
+        };
+        let refs = history::collect_refs(commits, &extractor);
+        summary.commits_with_keys = refs.len();
+
+        let checkpoint_path = match checkpoint_path {
+            Some(path) => path,
+            None => self.git_ops.git_path("jira_git_helper/sync-history").await?,
+        };
+        let mut checkpoint = history::Checkpoint::load(&checkpoint_path, mode)?;
+        let (resumed, pending): (Vec<_>, Vec<_>) = refs.into_iter().partition(|r| checkpoint.contains(&r.commit.hash));
+        summary.commits_resumed = resumed.len();
+
+        // 先确认所有 key 是否存在，避免对每个提交重复请求
+        let keys = history::distinct_keys(&pending);
+        let statuses: Vec<(String, Result<Option<issue::IssueStatus>, AppError>)> = futures::stream::iter(keys)
+            .map(|key| async move {
+                let status = self.jira_client.get_issue_status(&key).await;
+                (key, status)
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        let mut valid_keys = std::collections::HashSet::new();
+        for (key, status) in statuses {
+            match status {
+                Ok(Some(_)) => {
+                    valid_keys.insert(key);
+                }
+                Ok(None) => {
+                    summary.unknown_keys.insert(key, Vec::new());
+                }
+                Err(e) => {
+                    summary.inaccessible_keys.insert(key, e.to_string());
+                }
+            }
+        }
+        for r in &pending {
+            for key in &r.keys {
+                if let Some(commits) = summary.unknown_keys.get_mut(key) {
+                    commits.push(r.commit.short_hash.clone());
+                }
+            }
+        }
+
+        let remote_settings: remote_link::RemoteLinkSettings = app_config::get_section(&self.config, "remote_link")?;
+        let repo = self
+            .git_ops
+            .remote_url(&remote_settings.remote)
+            .await?
+            .and_then(|url| remote::RemoteRepo::parse(&url));
+        if mode == history::SyncMode::Link && repo.is_none() {
+            return Err(AppError::Git(format!("Cannot build web links for remote '{}'", remote_settings.remote)));
+        }
+
+        // 无法访问的 key 可能在权限修复后可用，这类提交不写入 checkpoint
+        let work: Vec<(git::CommitInfo, Vec<String>, bool)> = pending
+            .into_iter()
+            .map(|r| {
+                let retry = r.keys.iter().any(|k| summary.inaccessible_keys.contains_key(k));
+                let keys = r.keys.into_iter().filter(|k| valid_keys.contains(k)).collect();
+                (r.commit, keys, retry)
+            })
+            .collect();
+
+        if dry_run {
+            let repo = repo.as_ref();
+            let remote_settings = &remote_settings;
+            let mut checks = futures::stream::iter(work.iter().filter(|(_, keys, _)| !keys.is_empty()))
+                .map(|(commit, keys, _)| async move {
+                    let mut synced = Vec::new();
+                    for key in keys {
+                        synced.push(self.history_synced(key, commit, mode, repo, remote_settings).await);
+                    }
+                    (commit, keys, synced)
+                })
+                .buffered(concurrency);
+
+            while let Some((commit, keys, synced)) = checks.next().await {
+                let mut missing: Vec<&str> = Vec::new();
+                for (key, synced) in keys.iter().zip(synced) {
+                    match synced {
+                        Ok(true) => summary.already_present += 1,
+                        Ok(false) => missing.push(key),
+                        Err(e) => summary.failures.push((commit.short_hash.clone(), format!("{}: {}", key, e))),
+                    }
+                }
+                if !missing.is_empty() {
+                    println!("{} {} -> {}", commit.short_hash, commit.subject, missing.join(", "));
+                    summary.created += missing.len();
+                }
+            }
+            println!();
+            println!("Dry run, nothing was changed.");
+            println!("{}", summary);
+            return Ok(());
+        }
+
+        let repo = repo.as_ref();
+        let remote_settings = &remote_settings;
+        let mut results = futures::stream::iter(work)
+            .map(|(commit, keys, retry)| async move {
+                let mut counts = (0, 0);
+                let mut result = Ok(());
+                for key in &keys {
+                    let is_new = match mode {
+                        history::SyncMode::Link => match remote_link::commit_link(repo.expect("checked above"), remote_settings, &commit) {
+                            Some(link) => self.jira_client.add_remote_link(key, &link).await,
+                            None => Err(AppError::Other("Unknown git host, set remote_link.provider".to_string())),
+                        },
+                        history::SyncMode::Comment => self.comment_history(key, &commit, repo, remote_settings).await,
+                    };
+                    match is_new {
+                        Ok(true) => counts.0 += 1,
+                        Ok(false) => counts.1 += 1,
+                        Err(e) => {
+                            result = Err(format!("{}: {}", key, e));
+                            break;
+                        }
+                    }
+                }
+                (commit, keys, retry, counts, result)
+            })
+            .buffer_unordered(concurrency);
+
+        while let Some((commit, keys, retry, (created, present), result)) = results.next().await {
+            summary.created += created;
+            summary.already_present += present;
+            match result {
+                Ok(()) => {
+                    if !retry {
+                        checkpoint.record(&checkpoint_path, &commit.hash)?;
+                    }
+                    if !keys.is_empty() {
+                        println!("{} -> {}", commit.short_hash, keys.join(", "));
+                    }
+                }
+                Err(e) => summary.failures.push((commit.short_hash.clone(), e)),
+            }
+        }
+
+        println!();
+        println!("{}", summary);
+        println!("Checkpoint: {} ({} commits)", checkpoint_path.display(), checkpoint.len());
+        Ok(())
+    }
+
+    /// Whether the commit is already linked or commented on the issue,
+    /// checked without changing anything.
+    async fn history_synced(
+        &self,
+        key: &str,
+        commit: &git::CommitInfo,
+        mode: history::SyncMode,
+        repo: Option<&remote::RemoteRepo>,
+        settings: &remote_link::RemoteLinkSettings,
+    ) -> Result<bool, AppError> {
+        match mode {
+            history::SyncMode::Link => match remote_link::commit_link(repo.expect("checked above"), settings, commit) {
+                Some(link) => self.jira_client.has_remote_link(key, &link.global_id).await,
+                None => Err(AppError::Other("Unknown git host, set remote_link.provider".to_string())),
+            },
+            history::SyncMode::Comment => self.comments_mention(key, commit).await,
+        }
+    }
+
+    /// Whether one of the latest comments contains the full commit hash,
+    /// which the history comment and commit URLs always include.
+    async fn comments_mention(&self, key: &str, commit: &git::CommitInfo) -> Result<bool, AppError> {
+        let comments = self.jira_client.get_comments(key, HISTORY_COMMENT_SCAN).await?;
+        Ok(comments
+            .iter()
+            .any(|c| serde_json::to_string(&c.body).is_ok_and(|body| body.contains(&commit.hash))))
+    }
+
+    /// Comments the commit on the issue unless a comment already mentions it.
+    /// Returns whether a comment was added.
+    async fn comment_history(
+        &self,
+        key: &str,
+        commit: &git::CommitInfo,
+        repo: Option<&remote::RemoteRepo>,
+        settings: &remote_link::RemoteLinkSettings,
+    ) -> Result<bool, AppError> {
+        if self.comments_mention(key, commit).await? {
+            return Ok(false);
+        }
+
+        let url = repo.and_then(|repo| repo.commit_url(settings.provider, &commit.hash));
+        let wiki = self.jira_client.api_version() < 3;
+        let text = commit_comment::render_comment(history::HISTORY_COMMENT_TEMPLATE, commit, "", url.as_deref(), wiki);
+        self.jira_client.add_comment(key, &text).await?;
+        Ok(true)
+    }
+
+    /// Links the branch from the branch's issues and each pushed commit from
+    /// the issues its message mentions. Every link is attempted; failures
+    /// are reported together afterwards.
+    async fn link_pushed(
+        &self,
+        settings: &remote_link::RemoteLinkSettings,
+        branch_name: &str,
+        keys: &[String],
+        commits: &[git::CommitInfo],
+    ) -> Result<(), AppError> {
+        let remote_url = self
+            .git_ops
+            .remote_url(&settings.remote)
+            .await?
+            .ok_or_else(|| AppError::Git(format!("Remote '{}' not found", settings.remote)))?;
+        let repo = remote::RemoteRepo::parse(&remote_url)
+            .ok_or_else(|| AppError::Git(format!("Cannot build web links for remote URL {}", remote_url)))?;
+        let extractor = issue_key::KeyExtractor::from_config(&self.config)?;
+
+        let mut links: Vec<(String, jira::RemoteLink)> = Vec::new();
+        if settings.branch && branch_name != "HEAD" {
+            let link = remote_link::branch_link(&repo, settings, branch_name)
+                .ok_or_else(|| AppError::Other(format!("Unknown git host {}, set remote_link.provider", repo.host)))?;
+            links.extend(keys.iter().map(|key| (key.clone(), link.clone())));
+        }
+        if settings.commit {
+            // 提交只关联其信息中提到的 issue，分支的 issue 由分支链接覆盖
+            for commit in commits {
+                let link = remote_link::commit_link(&repo, settings, commit)
+                    .ok_or_else(|| AppError::Other(format!("Unknown git host {}, set remote_link.provider", repo.host)))?;
+                links.extend(extractor.extract_from_text(&commit.message).into_iter().map(|key| (key, link.clone())));
+            }
+        }
+
+        let mut added = 0;
+        let mut failures: Vec<String> = Vec::new();
+        for (key, link) in &links {
+            match self.jira_client.add_remote_link(key, link).await {
+                Ok(_) => added += 1,
+                Err(e) => failures.push(format!("{}: {}", key, e)),
+            }
+        }
+        if added > 0 {
+            println!("Added {} remote link(s) to JIRA", added);
+        }
+        if !failures.is_empty() {
+            return Err(AppError::JiraApi(format!("{} link(s) failed ({})", failures.len(), failures.join("; "))));
+        }
+        Ok(())
+    }
+
+    /// Asks for the time spent when `worklog.prompt` is set; an empty answer
+    /// skips the worklog.
+    fn prompt_time_spent(&self) -> Result<Option<String>, AppError> {
+        let settings: worklog::WorklogSettings = app_config::get_section(&self.config, "worklog")?;
+        if !settings.prompt {
+            return Ok(None);
+        }
+
+        loop {
+            let input = input::prompt_for_input("Time spent (e.g. 1h30m, empty to skip)", None)?;
+            if input.is_empty() {
+                return Ok(None);
+            }
+            match worklog::normalize_duration(&input) {
+                Ok(time_spent) => return Ok(Some(time_spent)),
+                Err(e) => println!("{}", e),
+            }
+        }
+    }
+
+    /// Logs the time spent on the first issue, started at the commit time
+    /// just made. The comment defaults to the commit subject.
+    async fn log_work(&self, issues: &[Issue], time_spent: &str, comment: Option<&str>) -> Result<(), AppError> {
+        let Some(issue) = issues.first() else {
+            return Ok(());
+        };
+        let commit = self.git_ops.head_commit().await?;
+        let comment = comment.unwrap_or(&commit.subject);
+
+        self.jira_client.add_worklog(&issue.key, time_spent, &commit.committed_at, comment).await?;
+        println!("Logged {} on {}", time_spent, issue.key);
+        Ok(())
+    }
+
+    /// Runs the Smart Commit commands for every issue and reports the result
+    /// of each.
+    async fn run_smart_commands(&self, issues: &[Issue], commands: &[smart_commit::SmartCommand]) {
+        if commands.is_empty() {
+            return;
+        }
+        let commit = match self.git_ops.head_commit().await {
+            Ok(commit) => commit,
+            Err(e) => {
+                println!("Warning: Smart Commit commands were not run: {}", e);
+                return;
+            }
+        };
+
+        println!("Smart Commit:");
+        for issue in issues {
+            for command in commands {
+                match self.run_smart_command(&issue.key, command, &commit).await {
+                    Ok(()) => println!("  {} {}: ok", issue.key, command.label()),
+                    Err(e) => println!("  {} {}: failed ({})", issue.key, command.label(), e),
+                }
+            }
+        }
+    }
+
+    async fn run_smart_command(&self, key: &str, command: &smart_commit::SmartCommand, commit: &git::CommitInfo) -> Result<(), AppError> {
+        match command {
+            smart_commit::SmartCommand::Comment(text) => self.jira_client.add_comment(key, text).await,
+            smart_commit::SmartCommand::Time { time_spent, comment } => {
+                let comment = if comment.is_empty() { &commit.subject } else { comment };
+                self.jira_client.add_worklog(key, time_spent, &commit.committed_at, comment).await
+            }
+            smart_commit::SmartCommand::Transition { name, comment } => {
+                let transitions = self.jira_client.get_transitions(key).await?;
+                let transition = workflow::find_command_transition(&transitions, name)
+                    .ok_or_else(|| AppError::JiraApi(format!("no transition '{}' is available", name)))?;
+                if !workflow::required_fields(transition).is_empty() {
+                    return Err(AppError::JiraApi(format!("'{}' needs more fields, use the transition command", transition.name)));
+                }
+                let comment = Some(comment.as_str()).filter(|c| !c.is_empty());
+                self.jira_client.transition_issue(key, &transition.id, &serde_json::Map::new(), comment).await
+            }
+        }
+    }
+
+    /// Comments on the issues about the commit just made, when
+    /// `commit_comment.enabled` is set.
+    async fn comment_commit(&self, branch_name: &str, issues: &[Issue]) -> Result<(), AppError> {
+        let settings: commit_comment::CommitCommentSettings = app_config::get_section(&self.config, "commit_comment")?;
+        if !settings.enabled {
+            return Ok(());
+        }
+
+        let commit = self.git_ops.head_commit().await?;
+        let url = self
+            .git_ops
+            .remote_url(&settings.remote)
+            .await?
+            .and_then(|remote| remote::RemoteRepo::parse(&remote))
+            .and_then(|repo| repo.commit_url(settings.provider, &commit.hash));
+        let wiki = self.jira_client.api_version() < 3;
+        let text = commit_comment::render_comment(&settings.template, &commit, branch_name, url.as_deref(), wiki);
+
+        for issue in issues {
+            self.jira_client.add_comment(&issue.key, &text).await?;
+            println!("Commented on {}", issue.key);
+        }
+        Ok(())
+    }
+
+    /// Moves the issues to `auto_transition.first_commit` when the commit
+    /// just made is the first one on the branch.
+    async fn after_commit(&self, branch_name: &str, issues: &[Issue]) -> Result<(), AppError> {
+        let settings: workflow::AutoTransitionSettings = app_config::get_section(&self.config, "auto_transition")?;
+        let Some(status) = &settings.first_commit else {
+            return Ok(());
+        };
+        if branch_name == "HEAD" || !matches!(self.git_ops.branch_commit_count(branch_name).await, Ok(1)) {
+            return Ok(());
+        }
+
+        for issue in issues {
+            self.auto_transition(&issue.key, status).await;
+        }
+        Ok(())
+    }
+
+    /// Moves an issue to the status for an automatic transition; failures
+    /// are reported as warnings so they never fail the git operation.
+    async fn auto_transition(&self, key: &str, status: &str) {
+        let result = match self.jira_client.get_issue_status(key).await {
+            Ok(current) => {
+                let current = current.map(|s| s.name).unwrap_or_default();
+                self.move_to_status(key, &current, status).await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            println!("Warning: could not move {} to {}: {}", key, status, e);
+        }
+    }
+
+    /// Moves an issue to the status unless it is already there. Transitions
+    /// whose screens need input are left to the `transition` command.
+    async fn move_to_status(&self, key: &str, current: &str, status: &str) -> Result<(), AppError> {
+        if current.eq_ignore_ascii_case(status) {
+            return Ok(());
+        }
+
+        let transitions = self.jira_client.get_transitions(key).await?;
+        match branch::find_transition(&transitions, status) {
+            Some(transition) if !workflow::required_fields(transition).is_empty() => {
+                println!(
+                    "Warning: moving {} to '{}' needs more fields, run 'jira_git_helper transition {} \"{}\"'",
+                    key, status, key, status
+                );
+            }
+            Some(transition) => {
+                self.jira_client.transition_issue(key, &transition.id, &serde_json::Map::new(), None).await?;
+                println!("Moved {} to {}", key, transition.to.name);
+            }
+            None => println!("Warning: no transition to '{}' is available for {}", status, key),
+        }
+        Ok(())
+    }
+
+    /// Keys of the issues the branch works on: from the branch name, or the
+    /// issues remembered for the branch in git config.
+    async fn branch_issue_keys(&self, branch_name: &str) -> Result<Vec<String>, AppError> {
+        let keys = issue_key::KeyExtractor::from_config(&self.config)?.extract(branch_name);
+        if !keys.is_empty() || branch_name == "HEAD" {
+            return Ok(keys);
+        }
+
+        let remembered = self.git_ops.get_config(&format!("branch.{}.jiraIssue", branch_name)).await?;
+        Ok(remembered
+            .map(|keys| keys.split(',').map(|key| key.trim().to_string()).collect())
+            .unwrap_or_default())
     }
-}
\ No newline at end of file
+
+    /// Searches issues with the filter and prints them as a table or JSON.
+    pub async fn list(&self, filter: &list::ListFilter, limit: usize, json: bool) -> Result<(), AppError> {
+        let jql = list::build_jql(filter);
+        log::debug!("JQL: {}", jql);
+        let issues = self.jira_client.search_issues(&jql, Some(limit)).await?;
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&issues)?);
+        } else if issues.is_empty() {
+            println!("No issues found.");
+        } else {
+            let width = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()).unwrap_or(120);
+            println!("{}", list::format_table(&issues, width));
+        }
+        Ok(())
+    }
+
+    /// Prints release notes for the issues mentioned by the commits of a range.
+    /// `format` and `group_by` override the `[release_notes]` settings.
+    pub async fn release_notes(
+        &self,
+        range: &str,
+        format: Option<release_notes::NotesFormat>,
+        group_by: Option<release_notes::GroupBy>,
+        output: Option<&Path>,
+    ) -> Result<(), AppError> {
+        let settings: release_notes::ReleaseNotesSettings = app_config::get_section(&self.config, "release_notes")?;
+        let (issues, unkeyed) = self.issues_in_range(range, &[]).await?;
+
+        let jira_url = self.config.get_string("jira_url")?;
+        let title = settings.title.replace("{range}", range);
+        let notes = release_notes::ReleaseNotes::build(
+            &title,
+            &issues,
+            &unkeyed,
+            group_by.unwrap_or(settings.group_by),
+            &jira_url,
+        );
+        let rendered = notes.render(format.unwrap_or(settings.format), &settings.template);
+
+        match output {
+            Some(path) => {
+                std::fs::write(path, rendered)?;
+                println!("Release notes written to {}", path.display());
+            }
+            None => print!("{}", rendered),
+        }
+        Ok(())
+    }
+
+    /// Adds a section for `version` to the changelog with the issues of the
+    /// commits since the tag of the last released version.
+    pub async fn changelog_update(
+        &self,
+        version: &str,
+        file: Option<PathBuf>,
+        date: Option<String>,
+        dry_run: bool,
+    ) -> Result<(), AppError> {
+        let settings: changelog::ChangelogSettings = app_config::get_section(&self.config, "changelog")?;
+        let path = file.unwrap_or_else(|| settings.file.clone());
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let range = match changelog::last_version(&content) {
+            Some(last) => {
+                let tag = settings.tag(&last);
+                if !self.git_ops.tag_exists(&tag).await? {
+                    return Err(AppError::Other(format!(
+                        "Tag {} of the last released version {} not found, check changelog.tag_format",
+                        tag, last
+                    )));
+                }
+                format!("{}..HEAD", tag)
+            }
+            None => "HEAD".to_string(),
+        };
+        println!("Collecting commits in {}", range);
+        let (issues, unkeyed) = self.issues_in_range(&range, &[]).await?;
+
+        let jira_url = self.config.get_string("jira_url")?;
+        let entries = changelog::entries(&issues, &settings, &jira_url);
+        let date = release_date(date)?;
+        let updated = changelog::update(&content, version, &settings.tag(version), &date, &entries)?;
+
+        if dry_run {
+            print!("{}", updated.section);
+        } else {
+            std::fs::write(&path, &updated.content)?;
+            println!("Added {} to {}", version, path.display());
+        }
+        if !unkeyed.is_empty() {
+            println!("{} commit(s) without an issue were not added.", unkeyed.len());
+        }
+        Ok(())
+    }
+
+    /// Creates the fix version in the project when missing and adds it to the
+    /// issues referenced in the range, optionally marking it released.
+    pub async fn release(
+        &self,
+        version: &str,
+        range: &str,
+        project: Option<String>,
+        date: Option<String>,
+        mark_released: bool,
+        dry_run: bool,
+    ) -> Result<(), AppError> {
+        let settings: release::ReleaseSettings = app_config::get_section(&self.config, "release")?;
+        let project = match project.or(settings.project) {
+            Some(project) => project,
+            None => self.default_project(&app_config::get_section(&self.config, "create")?)?,
+        };
+        let name = settings.version_name.replace("{version}", version);
+        let date = release_date(date)?;
+
+        // 先确定要修改的 issue，再创建版本
+        let (issues, _) = self.issues_in_range(range, &[]).await?;
+        let plan = release::plan(&issues, &project, &name);
+
+        let mut report = release::ReleaseReport {
+            version: name.clone(),
+            dry_run,
+            ..Default::default()
+        };
+        let versions = self.jira_client.get_project_versions(&project).await?;
+        let existing = match versions.into_iter().find(|v| v.name == name) {
+            Some(existing) => Some(existing),
+            None if dry_run => {
+                report.created = true;
+                None
+            }
+            None => {
+                report.created = true;
+                Some(self.jira_client.create_version(&project, &name, &date).await?)
+            }
+        };
+
+        for (key, action) in plan {
+            if action == release::IssueAction::AddVersion && !dry_run {
+                if let Err(e) = self.jira_client.add_fix_version(&key, &name).await {
+                    report.failures.push((key, e.to_string()));
+                    continue;
+                }
+            }
+            report.record(key, action);
+        }
+
+        if !report.failures.is_empty() {
+            println!("{}", report);
+            let skipped = if mark_released { ", the version was not released" } else { "" };
+            return Err(AppError::JiraApi(format!(
+                "{} issue(s) could not be updated{}",
+                report.failures.len(),
+                skipped
+            )));
+        }
+
+        if mark_released && !existing.as_ref().is_some_and(|v| v.released) {
+            if let Some(existing) = existing.as_ref().filter(|_| !dry_run) {
+                self.jira_client.release_version(&existing.id, &date).await?;
+            }
+            report.released = true;
+        }
+
+        println!("{}", report);
+        Ok(())
+    }
+
+    /// Suggests the next semantic version from the issues of the commits
+    /// since the latest version tag, and creates its tag when asked.
+    pub async fn next_version(&self, create_tag: bool) -> Result<(), AppError> {
+        let settings: next_version::NextVersionSettings = app_config::get_section(&self.config, "next_version")?;
+        let tags = self.git_ops.merged_tags().await?;
+        let (range, current) = match next_version::latest_version(&tags, &settings.tag_prefix) {
+            Some((tag, version)) => {
+                println!("Latest version: {} (tag {})", version, tag);
+                (format!("{}..HEAD", tag), version)
+            }
+            None => {
+                println!("No version tag found, starting from 0.0.0");
+                ("HEAD".to_string(), semver::Version::new(0, 0, 0))
+            }
+        };
+
+        let (issues, unkeyed) = self.issues_in_range(&range, &settings.fields()).await?;
+        if issues.is_empty() && unkeyed.is_empty() {
+            println!("No commits since the latest version.");
+            return Ok(());
+        }
+
+        let suggestion = next_version::suggest(&current, &issues, unkeyed.len(), &settings);
+        for reason in &suggestion.reasons {
+            println!("  {:<5}  {} ({})", reason.bump, reason.subject, reason.why);
+        }
+        println!("Suggested version: {} ({})", suggestion.next, suggestion.bump);
+
+        if create_tag {
+            let tag = settings.tag(&suggestion.next);
+            self.git_ops.create_tag(&tag, &format!("Release {}", suggestion.next)).await?;
+            println!("Created tag {}", tag);
+        }
+        Ok(())
+    }
+
+    /// Checks the JIRA references of the commits in a range and fails when
+    /// any commit has a problem, so it can gate merges.
+    pub async fn audit(
+        &self,
+        range: &str,
+        format: audit::AuditFormat,
+        fix_version: Option<String>,
+        output: Option<&Path>,
+    ) -> Result<(), AppError> {
+        let mut settings: audit::AuditSettings = app_config::get_section(&self.config, "audit")?;
+        let key_settings: issue_key::KeyExtractionSettings = app_config::get_section(&self.config, "key_extraction")?;
+        settings.fill_projects(&key_settings.projects, self.config.get_string("jira_id_prefix").ok().as_deref());
+        let commits = self.git_ops.log(&["--reverse", "--no-merges", range]).await?;
+        let keys = audit::commit_keys(&commits, &key_settings)?;
+
+        let mut distinct: Vec<String> = keys.iter().flatten().cloned().collect();
+        distinct.sort();
+        distinct.dedup();
+        let issues: std::collections::HashMap<String, Issue> = self
+            .jira_client
+            .get_issues_by_keys(&distinct, &[])
+            .await?
+            .into_iter()
+            .map(|issue| (issue.key.clone(), issue))
+            .collect();
+
+        let fix_version = fix_version.or(settings.fix_version.clone());
+        let report = audit::audit(range, &commits, &keys, &issues, fix_version.as_deref(), &settings)?;
+        let rendered = report.render(format);
+        match output {
+            Some(path) => {
+                std::fs::write(path, rendered)?;
+                println!("Audit report written to {}", path.display());
+            }
+            None => print!("{}", rendered),
+        }
+
+        let failed = report.failed().count();
+        if failed > 0 {
+            return Err(AppError::AuditFailed(format!("{} of {} commits have problems", failed, commits.len())));
+        }
+        Ok(())
+    }
+
+    /// Runs `git log` with the arguments and prints every commit with the key,
+    /// status and summary of its issue. Issues are looked up in batches and
+    /// cached in the git directory for `log.cache_ttl` seconds.
+    pub async fn log(&self, filter: issue_log::LogFilter, graph: bool, no_cache: bool, args: &[String]) -> Result<(), AppError> {
+        let settings: issue_log::LogSettings = app_config::get_section(&self.config, "log")?;
+        issue_log::check_git_args(args)?;
+        let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+        if graph {
+            // --graph 隐含 --topo-order，两次 git log 需要选出相同的提交
+            args.insert(0, "--topo-order");
+        }
+        let commits = self.git_ops.log(&args).await?;
+        let extractor = issue_key::KeyExtractor::from_config(&self.config)?;
+        let commit_keys: std::collections::HashMap<&str, Vec<String>> = commits
+            .iter()
+            .map(|c| (c.hash.as_str(), extractor.extract_from_text(&c.message)))
+            .collect();
+
+        let use_cache = settings.cache && !no_cache;
+        let cache_path = self.git_ops.git_path("jira_git_helper/issue-cache.json").await?;
+        let mut cache = if use_cache {
+            issue_log::IssueCache::load(&cache_path)
+        } else {
+            issue_log::IssueCache::default()
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let mut keys: Vec<String> = commit_keys.values().flatten().cloned().collect();
+        keys.sort();
+        keys.dedup();
+        let mut issues = std::collections::HashMap::new();
+        let mut uncached = Vec::new();
+        for key in keys {
+            match cache.get(&key, now, settings.cache_ttl) {
+                Some(issue) => {
+                    issues.insert(key, issue.clone());
+                }
+                None => uncached.push(key),
+            }
+        }
+        for issue in self.jira_client.get_issues_by_keys(&uncached, &[]).await? {
+            let cached = issue_log::CachedIssue::new(&issue, now);
+            cache.insert(&issue.key, cached.clone());
+            issues.insert(issue.key, cached);
+        }
+        if use_cache {
+            cache.prune(now, settings.cache_ttl);
+            if let Err(e) = cache.save(&cache_path) {
+                eprintln!("Warning: could not save the issue cache: {}", e);
+            }
+        }
+
+        let mut filter = filter;
+        if filter.assignee.as_deref() == Some("me") {
+            let me = self.jira_client.get_myself().await?;
+            filter.assignee = me.account_id.or(me.name).or(Some(me.display_name));
+        }
+
+        let keys_of = |commit: &git::CommitInfo| commit_keys.get(commit.hash.as_str()).map_or(&[][..], Vec::as_slice);
+        let lines: Vec<issue_log::LogLine> = if graph {
+            let by_hash: std::collections::HashMap<&str, &git::CommitInfo> = commits.iter().map(|c| (c.hash.as_str(), c)).collect();
+            self.git_ops
+                .log_graph(&args)
+                .await?
+                .into_iter()
+                .map(|(graph, hash)| {
+                    let commit = hash.and_then(|h| by_hash.get(h.as_str()).copied());
+                    let keys = commit.map_or(&[][..], keys_of);
+                    issue_log::LogLine {
+                        graph,
+                        commit,
+                        keys,
+                        hidden: commit.is_some() && !filter.matches(keys, &issues),
+                    }
+                })
+                .collect()
+        } else {
+            commits
+                .iter()
+                .filter(|c| filter.matches(keys_of(c), &issues))
+                .map(|c| issue_log::LogLine {
+                    graph: String::new(),
+                    commit: Some(c),
+                    keys: keys_of(c),
+                    hidden: false,
+                })
+                .collect()
+        };
+
+        if lines.iter().all(|l| l.commit.is_none() || l.hidden) {
+            println!("No commits found.");
+        } else {
+            let width = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()).unwrap_or(120);
+            println!("{}", issue_log::format_lines(&lines, &issues, width));
+        }
+        Ok(())
+    }
+
+    /// Shows how the summary pipeline transforms the title of an issue.
+    pub async fn preview_summary(&self, reference: &str) -> Result<(), AppError> {
+        let key = issue_key::parse_issue_reference(reference)
+            .ok_or_else(|| AppError::Other(format!("Not a JIRA issue key or URL: {}", reference)))?;
+        let title = self.jira_client.get_issue_title(&key).await?;
+        let sanitized = self.sanitizer()?.apply(&title);
+
+        println!("JIRA ID: {}", key);
+        println!("Before: {}", title);
+        println!("After:  {}", sanitized);
+        Ok(())
+    }
+
+    /// Issues mentioned by the non-merge commits of a range, with the extra
+    /// fields, and the commits that mention none.
+    async fn issues_in_range(
+        &self,
+        range: &str,
+        extra_fields: &[String],
+    ) -> Result<(Vec<Issue>, Vec<git::CommitInfo>), AppError> {
+        let commits = self.git_ops.log(&["--reverse", "--no-merges", range]).await?;
+        let extractor = issue_key::KeyExtractor::from_config(&self.config)?;
+        let (keyed, unkeyed): (Vec<_>, Vec<_>) = commits
+            .into_iter()
+            .partition(|c| !extractor.extract_from_text(&c.message).is_empty());
+        let keys = history::distinct_keys(&history::collect_refs(keyed, &extractor));
+
+        let issues = self.jira_client.get_issues_by_keys(&keys, extra_fields).await?;
+        let missing: Vec<&str> = keys
+            .iter()
+            .filter(|k| !issues.iter().any(|i: &Issue| &i.key == *k))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            eprintln!("Warning: issues not found or not visible: {}", missing.join(", "));
+        }
+        Ok((issues, unkeyed))
+    }
+
+    fn sanitizer(&self) -> Result<sanitize::Sanitizer, AppError> {
+        let settings: sanitize::SummarySettings = app_config::get_section(&self.config, "summary")?;
+        sanitize::Sanitizer::new(&settings)
+    }
+
+    async fn build_commit_message(&self, issues: &[Issue], additional_message: &str) -> Result<String, AppError> {
+        let trailer_settings: message::TrailerSettings = app_config::get_section(&self.config, "trailer")?;
+        let default_template = if trailer_settings.enabled {
+            message::DEFAULT_TRAILER_TEMPLATE
+        } else {
+            message::DEFAULT_TEMPLATE
+        };
+        let template = self.config.get_string("message_template")
+            .unwrap_or_else(|_| default_template.to_string());
+        let subject_settings: message::SubjectSettings = app_config::get_section(&self.config, "subject")?;
+        let commit_message = message::render_with_limit(&template, issues, additional_message, &subject_settings);
+
+        if !trailer_settings.enabled {
+            return Ok(commit_message);
+        }
+
+        let jira_url = self.config.get_string("jira_url")?;
+        let trailers = message::build_trailers(&trailer_settings, issues, &jira_url);
+        self.git_ops.add_trailers(&commit_message, &trailers).await
+    }
+
+    /// Runs as git's `commit-msg` hook: validates the message file and
+    /// optionally verifies the referenced issues with JIRA. Only reads the
+    /// configuration; the JIRA client is built when `verify` is on.
+    pub async fn check_commit_msg(message_file: &Path) -> Result<(), AppError> {
+        let config = app_config::load_config()?;
+        let mut settings: hook::CommitMsgSettings = app_config::get_section(&config, "commit_msg")?;
+        if settings.projects.is_empty() {
+            if let Ok(prefix) = config.get_string("jira_id_prefix") {
+                settings.projects.push(prefix);
+            }
+        }
+
+        let message = std::fs::read_to_string(message_file)?;
+        let keys = hook::validate_message(&message, &settings)?;
+
+        if !settings.verify || keys.is_empty() {
+            return Ok(());
+        }
+
+        if !app_config::has_password(&config) {
+            return Err(AppError::ConfigMissing);
+        }
+        let jira_client = jira::JiraClient::new(&config)?;
+
+        for key in &keys {
+            let lookup = tokio::time::timeout(
+                Duration::from_secs(settings.timeout_secs),
+                jira_client.get_issue_status(key),
+            )
+            .await;
+
+            match lookup {
+                Ok(Ok(Some(status))) => {
+                    if hook::is_closed(&status, &settings) {
+                        return Err(AppError::CommitRejected(format!("{} is already {}", key, status.name)));
+                    }
+                }
+                Ok(Ok(None)) => {
+                    return Err(AppError::CommitRejected(format!("{} does not exist", key)));
+                }
+                Ok(Err(e)) => return Self::handle_offline(&settings, &e.to_string()),
+                Err(_) => return Self::handle_offline(&settings, "request timed out"),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_offline(settings: &hook::CommitMsgSettings, reason: &str) -> Result<(), AppError> {
+        match settings.offline {
+            hook::OfflinePolicy::Allow => Ok(()),
+            hook::OfflinePolicy::Warn => {
+                eprintln!("Warning: could not verify JIRA issues ({}), continuing.", reason);
+                Ok(())
+            }
+            hook::OfflinePolicy::Block => Err(AppError::CommitRejected(format!("could not verify JIRA issues ({})", reason))),
+        }
+    }
+
+    /// Fetches the summaries of all keys concurrently and, when there is more
+    /// than one, lets the user choose which issues the commit refers to.
+    async fn select_issues(&self, jira_ids: &[String]) -> Result<Vec<Issue>, AppError> {
+        let fetched = futures::future::join_all(
+            jira_ids.iter().map(|id| self.jira_client.get_issue(id)),
+        )
+        .await;
+
+        let mut issues = Vec::new();
+        let mut first_error = None;
+        for (key, result) in jira_ids.iter().zip(fetched) {
+            match result {
+                Ok(issue) => issues.push(issue),
+                Err(e) => {
+                    println!("Skipping {}: {}", key, e);
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if issues.is_empty() {
+            return Err(first_error.unwrap_or_else(|| AppError::Other("No JIRA issue available".to_string())));
+        }
+        if issues.len() == 1 {
+            return Ok(issues);
+        }
+
+        let items: Vec<String> = issues.iter().map(|i| format!("{}  {}", i.key, i.summary)).collect();
+        let selected = input::select_items("Select issues (e.g. 1,3)", &items, "all")?;
+        Ok(selected.into_iter().map(|i| issues[i].clone()).collect())
+    }
+
+    /// Finds the issues for the commit, trying in order: the `--issue`
+    /// argument, keys in the branch name, the issue remembered for the branch
+    /// in git config, keys in the most recent commits, and finally a picker of
+    /// the user's open assigned issues.
+    async fn resolve_issues(&self, branch_name: &str, explicit: Option<&str>) -> Result<Vec<Issue>, AppError> {
+        let extractor = issue_key::KeyExtractor::from_config(&self.config)?;
+        let remember_key = format!("branch.{}.jiraIssue", branch_name);
+        let can_remember = branch_name != "HEAD";
+
+        if let Some(reference) = explicit {
+            let key = issue_key::parse_issue_reference(reference)
+                .ok_or_else(|| AppError::Other(format!("Not a JIRA issue key or URL: {}", reference)))?;
+            let issues = self.select_issues(&[key]).await?;
+            if can_remember && extractor.extract(branch_name).is_empty() {
+                self.remember_issues(&remember_key, &issues).await?;
+            }
+            return Ok(issues);
+        }
+
+        let ids = extractor.extract(branch_name);
+        if !ids.is_empty() {
+            return self.select_issues(&ids).await;
+        }
+
+        if can_remember {
+            if let Some(remembered) = self.git_ops.get_config(&remember_key).await? {
+                let ids: Vec<String> = remembered.split(',').map(|id| id.trim().to_string()).collect();
+                println!("Using {} remembered for branch {}", remembered, branch_name);
+                return self.select_issues(&ids).await;
+            }
+        }
+
+        for message in self.git_ops.recent_commit_messages(branch_name, RECENT_COMMITS).await? {
+            let ids = extractor.extract_from_text(&message);
+            if !ids.is_empty() {
+                println!("Using {} from recent commits", ids.join(", "));
+                return self.select_issues(&ids).await;
+            }
+        }
+
+        println!("JIRA ID not found in branch name. Your open issues:");
+        let jql = self.config.get_string("issue_picker_jql").unwrap_or_else(|_| DEFAULT_PICKER_JQL.to_string());
+        let candidates = self.jira_client.search_issues(&jql, Some(20)).await?;
+        if candidates.is_empty() {
+            return Err(AppError::Other("JIRA ID not found in branch name and no open issues are assigned to you".to_string()));
+        }
+
+        let items: Vec<String> = candidates.iter().map(|i| format!("{}  {}", i.key, i.summary)).collect();
+        let selected = input::select_items("Select issues (e.g. 1,3)", &items, "1")?;
+        let issues: Vec<Issue> = selected.into_iter().map(|i| candidates[i].clone()).collect();
+        if can_remember {
+            self.remember_issues(&remember_key, &issues).await?;
+        }
+        Ok(issues)
+    }
+
+    async fn remember_issues(&self, remember_key: &str, issues: &[Issue]) -> Result<(), AppError> {
+        let keys: Vec<&str> = issues.iter().map(|i| i.key.as_str()).collect();
+        self.git_ops.set_config(remember_key, &keys.join(",")).await
+    }
+}
+
+/// Asks for the value of a screen field: a choice among the allowed values,
+/// or text converted according to the field schema.
+fn prompt_field(id: &str, field: &jira::FieldMeta) -> Result<serde_json::Value, AppError> {
+    let label = if field.name.is_empty() { id } else { field.name.as_str() };
+    if !field.allowed_values.is_empty() {
+        println!("{}:", label);
+        let items: Vec<String> = field.allowed_values.iter().map(workflow::allowed_value_label).collect();
+        let index = input::select_item(&format!("Select {}", label), &items, "1")?;
+        return Ok(create::selected_value(field, index));
+    }
+
+    loop {
+        match create::field_value(field, &prompt_required(label)?) {
+            Ok(value) => return Ok(value),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+/// The release date given as `YYYY-MM-DD`, today when not given.
+fn release_date(date: Option<String>) -> Result<String, AppError> {
+    match date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .map_err(|_| AppError::Other(format!("Invalid date '{}', expected YYYY-MM-DD", date))),
+        None => Ok(chrono::Local::now().format("%Y-%m-%d").to_string()),
+    }
+}
+
+/// Prompts until a non-empty value is entered.
+fn prompt_required(label: &str) -> Result<String, AppError> {
+    loop {
+        let value = input::prompt_for_input(label, None)?;
+        if !value.is_empty() {
+            return Ok(value);
+        }
+        println!("{} is required.", label);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::issue::Issue;
+use crate::message::{display_width, truncate_at_word};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Filters of the `list` command. Presets are combined with `AND`.
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    pub mine: bool,
+    pub sprint: bool,
+    pub project: Option<String>,
+    pub status: Option<String>,
+    /// Raw JQL, combined with the presets.
+    pub jql: Option<String>,
+}
+
+/// Used when no filter is given.
+const DEFAULT_JQL: &str = "assignee = currentUser() AND resolution = Unresolved";
+const DEFAULT_ORDER: &str = "ORDER BY updated DESC";
+
+/// Builds the JQL query for the filter.
+pub fn build_jql(filter: &ListFilter) -> String {
+    let (raw, order) = match filter.jql.as_deref().map(split_order_by) {
+        Some((raw, order)) => (Some(raw), order),
+        None => (None, None),
+    };
+
+    let mut clauses: Vec<String> = Vec::new();
+    if let Some(raw) = raw.filter(|r| !r.is_empty()) {
+        clauses.push(format!("({})", raw));
+    }
+    if filter.mine {
+        clauses.push("assignee = currentUser()".to_string());
+    }
+    if filter.sprint {
+        clauses.push("sprint in openSprints()".to_string());
+    }
+    if let Some(project) = &filter.project {
+        clauses.push(format!("project = {}", quote(project)));
+    }
+    if let Some(status) = &filter.status {
+        clauses.push(format!("status = {}", quote(status)));
+    }
+
+    let condition = if clauses.is_empty() {
+        DEFAULT_JQL.to_string()
+    } else {
+        clauses.join(" AND ")
+    };
+    format!("{} {}", condition, order.unwrap_or(DEFAULT_ORDER))
+}
+
+/// Separates a trailing `ORDER BY` clause from raw JQL. Text inside quoted
+/// strings (`summary ~ "order by"`) is skipped.
+fn split_order_by(jql: &str) -> (&str, Option<&str>) {
+    lazy_static! {
+        static ref ORDER_BY: Regex = Regex::new(r"(?i)\bORDER\s+BY\b").unwrap();
+    }
+    let found = ORDER_BY
+        .find_iter(jql)
+        .map(|m| m.start())
+        .filter(|&index| !in_quotes(&jql[..index]))
+        .last();
+    match found {
+        Some(index) => (jql[..index].trim(), Some(jql[index..].trim())),
+        None => (jql.trim(), None),
+    }
+}
+
+/// Whether the end of the text lies inside a quoted string.
+fn in_quotes(text: &str) -> bool {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for c in text.chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None => {}
+        }
+    }
+    quote.is_some()
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Formats issues as a compact table no wider than `width` columns; the
+/// summary column takes the remaining space.
+pub fn format_table(issues: &[Issue], width: usize) -> String {
+    let headers = ["KEY", "TYPE", "STATUS", "PRIORITY", "ASSIGNEE", "SUMMARY"];
+    let rows: Vec<[String; 6]> = issues
+        .iter()
+        .map(|issue| {
+            [
+                issue.key.clone(),
+                issue.issue_type.clone().unwrap_or_default(),
+                issue.status_name().to_string(),
+                issue.priority.clone().unwrap_or_default(),
+                issue.assignee_name().to_string(),
+                issue.summary.clone(),
+            ]
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|h| display_width(h)).collect();
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate().take(5) {
+            widths[i] = widths[i].max(display_width(cell));
+        }
+    }
+    let fixed: usize = widths.iter().take(5).map(|w| w + 2).sum();
+    let summary_width = width.saturating_sub(fixed).max(20);
+
+    let format_row = |cells: &[String]| {
+        let mut line = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            if i == 5 {
+                line.push_str(&truncate_at_word(cell, summary_width, "…"));
+            } else {
+                line.push_str(cell);
+                line.push_str(&" ".repeat(widths[i] - display_width(cell) + 2));
+            }
+        }
+        line.trim_end().to_string()
+    };
+
+    let mut lines = vec![format_row(&headers.map(str::to_string))];
+    lines.extend(rows.iter().map(|row| format_row(row)));
+    lines.join("\n")
+}
+
+/// JQL matching the given issue keys, for fetching them in one search.
+pub fn keys_jql(keys: &[String]) -> String {
+    format!("key in ({}) ORDER BY key ASC", keys.join(", "))
+}
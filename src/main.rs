@@ -1,5 +1,12 @@
 use clap::{Command, Arg, ArgAction};
-use jira_git_helper::{AppError, App, app_config};
+use jira_git_helper::{AppError, App, CommitOptions, app_config};
+use jira_git_helper::audit::AuditFormat;
+use jira_git_helper::create::NewIssue;
+use jira_git_helper::history::SyncMode;
+use jira_git_helper::issue_log::LogFilter;
+use jira_git_helper::list::ListFilter;
+use jira_git_helper::release_notes::{GroupBy, NotesFormat};
+use std::path::{Path, PathBuf};
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
@@ -27,6 +34,298 @@ async fn main() -> Result<(), AppError> {
             .long("reset")
             .help("Reset all configurations")
             .action(ArgAction::SetTrue))
+        .arg(Arg::new("issue")
+            .short('i')
+            .long("issue")
+            .value_name("KEY|URL")
+            .help("JIRA issue to commit against, as a key or browse URL"))
+        .arg(Arg::new("time")
+            .short('t')
+            .long("time")
+            .value_name("DURATION")
+            .help("Log work on the issue after committing, e.g. 1h30m"))
+        .arg(Arg::new("time_comment")
+            .long("time-comment")
+            .value_name("TEXT")
+            .help("Worklog comment (defaults to the commit subject)"))
+        .subcommand(Command::new("commit-msg")
+            .about("Validate a commit message file (for use as git's commit-msg hook)")
+            .arg(Arg::new("file")
+                .help("Path to the commit message file")
+                .required(true)
+                .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(Command::new("preview")
+            .about("Show how the summary of an issue is cleaned up before it is used")
+            .arg(Arg::new("issue")
+                .help("JIRA issue key or browse URL")
+                .required(true)))
+        .subcommand(Command::new("show")
+            .about("Show an issue in the terminal (defaults to the key in the branch name)")
+            .arg(Arg::new("issue")
+                .help("JIRA issue key or browse URL"))
+            .arg(Arg::new("comments")
+                .short('n')
+                .long("comments")
+                .help("Number of recent comments to show")
+                .default_value("5")
+                .value_parser(clap::value_parser!(u32))))
+        .subcommand(Command::new("list")
+            .about("List JIRA issues")
+            .arg(Arg::new("mine")
+                .long("mine")
+                .help("Only issues assigned to me")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("sprint")
+                .long("sprint")
+                .help("Only issues in open sprints")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("project")
+                .long("project")
+                .value_name("KEY")
+                .help("Only issues in this project"))
+            .arg(Arg::new("status")
+                .long("status")
+                .value_name("STATUS")
+                .help("Only issues with this status"))
+            .arg(Arg::new("jql")
+                .long("jql")
+                .value_name("JQL")
+                .help("Raw JQL query, combined with the other filters"))
+            .arg(Arg::new("limit")
+                .long("limit")
+                .help("Maximum number of issues")
+                .default_value("50")
+                .value_parser(clap::value_parser!(usize)))
+            .arg(Arg::new("json")
+                .long("json")
+                .help("Print issues as JSON")
+                .action(ArgAction::SetTrue)))
+        .subcommand(Command::new("start")
+            .about("Create a branch for an issue and move the issue to In Progress")
+            .arg(Arg::new("issue")
+                .help("JIRA issue key or browse URL")
+                .required(true))
+            .arg(Arg::new("base")
+                .long("base")
+                .value_name("BRANCH")
+                .help("Branch to start from (overrides start.base_branch)")))
+        .subcommand(Command::new("transition")
+            .about("Move an issue to another status (defaults to the key in the branch name)")
+            .arg(Arg::new("issue")
+                .help("JIRA issue key or browse URL"))
+            .arg(Arg::new("status")
+                .help("Target status or transition name; picked interactively when omitted"))
+            .arg(Arg::new("comment")
+                .short('m')
+                .long("comment")
+                .value_name("TEXT")
+                .help("Comment to add with the transition")))
+        .subcommand(Command::new("create")
+            .about("Create a JIRA issue, optionally starting work on it")
+            .arg(Arg::new("type")
+                .long("type")
+                .value_name("TYPE")
+                .help("Issue type, e.g. Bug (defaults to create.issue_type, otherwise asked)"))
+            .arg(Arg::new("summary")
+                .short('s')
+                .long("summary")
+                .value_name("TEXT")
+                .help("Issue summary"))
+            .arg(Arg::new("project")
+                .short('p')
+                .long("project")
+                .value_name("KEY")
+                .help("Project key (defaults to create.project)"))
+            .arg(Arg::new("description")
+                .short('d')
+                .long("description")
+                .value_name("TEXT")
+                .help("Issue description"))
+            .arg(Arg::new("edit")
+                .short('e')
+                .long("edit")
+                .help("Write the description in $EDITOR")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("label")
+                .long("label")
+                .value_name("LABEL")
+                .help("Label to add (repeatable)")
+                .action(ArgAction::Append))
+            .arg(Arg::new("component")
+                .long("component")
+                .value_name("NAME")
+                .help("Component to add (repeatable)")
+                .action(ArgAction::Append))
+            .arg(Arg::new("priority")
+                .long("priority")
+                .value_name("NAME")
+                .help("Priority, e.g. High"))
+            .arg(Arg::new("parent")
+                .long("parent")
+                .value_name("KEY")
+                .help("Parent issue, for subtasks"))
+            .arg(Arg::new("start")
+                .long("start")
+                .help("Create a branch for the new issue and move it to In Progress")
+                .action(ArgAction::SetTrue)))
+        .subcommand(Command::new("sync-history")
+            .about("Link (or comment) the commits of a range on the issues they mention")
+            .arg(Arg::new("range")
+                .help("Revision range, e.g. v1.0..HEAD")
+                .required(true))
+            .arg(Arg::new("mode")
+                .long("mode")
+                .help("Create remote links or comments")
+                .default_value("link")
+                .value_parser(["link", "comment"]))
+            .arg(Arg::new("dry_run")
+                .long("dry-run")
+                .help("Only report what would be created")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("concurrency")
+                .long("concurrency")
+                .help("Maximum number of concurrent JIRA requests")
+                .default_value("4")
+                .value_parser(clap::value_parser!(usize)))
+            .arg(Arg::new("checkpoint")
+                .long("checkpoint")
+                .value_name("FILE")
+                .help("Checkpoint file for resuming (defaults to a file in the git directory)")
+                .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(Command::new("release-notes")
+            .about("Generate release notes from the issues mentioned in a range of commits")
+            .arg(Arg::new("range")
+                .help("Revision range, e.g. v1.0..v1.1")
+                .required(true))
+            .arg(Arg::new("format")
+                .long("format")
+                .short('f')
+                .help("Output format (defaults to release_notes.format)")
+                .value_parser(["markdown", "html", "text", "json", "template"]))
+            .arg(Arg::new("group_by")
+                .long("group-by")
+                .help("Group issues by type or component (defaults to release_notes.group_by)")
+                .value_parser(["type", "component"]))
+            .arg(Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_name("FILE")
+                .help("Write the notes to a file instead of stdout")
+                .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(Command::new("changelog")
+            .about("Maintain CHANGELOG.md in Keep a Changelog format")
+            .subcommand_required(true)
+            .subcommand(Command::new("update")
+                .about("Add a version section with the issues of the commits since the last release")
+                .arg(Arg::new("version")
+                    .long("version")
+                    .value_name("X.Y.Z")
+                    .help("Version of the new section")
+                    .required(true))
+                .arg(Arg::new("file")
+                    .long("file")
+                    .value_name("FILE")
+                    .help("Changelog file (defaults to changelog.file)")
+                    .value_parser(clap::value_parser!(PathBuf)))
+                .arg(Arg::new("date")
+                    .long("date")
+                    .value_name("YYYY-MM-DD")
+                    .help("Release date (defaults to today)"))
+                .arg(Arg::new("dry_run")
+                    .long("dry-run")
+                    .help("Print the new section instead of writing the file")
+                    .action(ArgAction::SetTrue))))
+        .subcommand(Command::new("release")
+            .about("Create a JIRA fix version and add it to the issues of a range")
+            .arg(Arg::new("version")
+                .help("Version to release, e.g. 1.2.0")
+                .required(true))
+            .arg(Arg::new("range")
+                .long("range")
+                .value_name("FROM..TO")
+                .help("Revision range of the release, e.g. v1.1.0..v1.2.0")
+                .required(true))
+            .arg(Arg::new("project")
+                .long("project")
+                .short('p')
+                .help("Project of the version (defaults to release.project)"))
+            .arg(Arg::new("date")
+                .long("date")
+                .value_name("YYYY-MM-DD")
+                .help("Release date (defaults to today)"))
+            .arg(Arg::new("released")
+                .long("released")
+                .help("Mark the version as released")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("dry_run")
+                .long("dry-run")
+                .help("Only report what would change")
+                .action(ArgAction::SetTrue)))
+        .subcommand(Command::new("next-version")
+            .about("Suggest the next semantic version from the issues since the latest version tag")
+            .arg(Arg::new("tag")
+                .long("tag")
+                .help("Create the tag of the suggested version")
+                .action(ArgAction::SetTrue)))
+        .subcommand(Command::new("audit")
+            .about("Report commits of a range with missing or wrong JIRA references")
+            .arg(Arg::new("range")
+                .help("Revision range, e.g. main..HEAD")
+                .required(true))
+            .arg(Arg::new("format")
+                .long("format")
+                .short('f')
+                .help("Output format")
+                .default_value("table")
+                .value_parser(["table", "json", "junit"]))
+            .arg(Arg::new("fix_version")
+                .long("fix-version")
+                .value_name("VERSION")
+                .help("Report issues planned for another fix version (defaults to audit.fix_version)"))
+            .arg(Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_name("FILE")
+                .help("Write the report to a file instead of stdout")
+                .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(Command::new("log")
+            .about("Show git log with the JIRA key, status and summary of every commit")
+            .arg(Arg::new("issue")
+                .long("issue")
+                .value_name("KEY")
+                .help("Only commits that mention this issue"))
+            .arg(Arg::new("status")
+                .long("status")
+                .help("Only commits whose issue has this status"))
+            .arg(Arg::new("assignee")
+                .long("assignee")
+                .help("Only commits whose issue is assigned to this user ('me' for yourself)"))
+            .arg(Arg::new("graph")
+                .long("graph")
+                .help("Draw the commit graph, as git log --graph")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("no_cache")
+                .long("no-cache")
+                .help("Fetch all issues from JIRA instead of using the cache")
+                .action(ArgAction::SetTrue))
+            .arg(Arg::new("max_count")
+                .long("max-count")
+                .short('n')
+                .help("Number of commits to show")
+                .default_value("100")
+                .value_parser(clap::value_parser!(usize)))
+            .arg(Arg::new("args")
+                .help("Revision range and other arguments passed to git log")
+                .num_args(0..)
+                .trailing_var_arg(true)
+                .allow_hyphen_values(true)))
+        .subcommand(Command::new("push")
+            .about("Run git push, then apply the auto_transition.push transition")
+            .arg(Arg::new("args")
+                .help("Arguments passed to git push")
+                .num_args(0..)
+                .trailing_var_arg(true)
+                .allow_hyphen_values(true)))
         .get_matches();
 
     if matches.get_flag("windows_help") {
@@ -41,8 +340,155 @@ async fn main() -> Result<(), AppError> {
         return handle_reset_command().await;
     }
 
+    if let Some(sub_matches) = matches.subcommand_matches("commit-msg") {
+        let file = sub_matches.get_one::<PathBuf>("file").expect("file is required");
+        return handle_commit_msg_command(file).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("preview") {
+        let issue = sub_matches.get_one::<String>("issue").expect("issue is required");
+        return App::new().await?.preview_summary(issue).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("show") {
+        let issue = sub_matches.get_one::<String>("issue").map(String::as_str);
+        let comments = *sub_matches.get_one::<u32>("comments").expect("has default");
+        return App::new().await?.show(issue, comments).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("list") {
+        let filter = ListFilter {
+            mine: sub_matches.get_flag("mine"),
+            sprint: sub_matches.get_flag("sprint"),
+            project: sub_matches.get_one::<String>("project").cloned(),
+            status: sub_matches.get_one::<String>("status").cloned(),
+            jql: sub_matches.get_one::<String>("jql").cloned(),
+        };
+        let limit = *sub_matches.get_one::<usize>("limit").expect("has default");
+        return App::new().await?.list(&filter, limit, sub_matches.get_flag("json")).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("start") {
+        let issue = sub_matches.get_one::<String>("issue").expect("issue is required");
+        let base = sub_matches.get_one::<String>("base").map(String::as_str);
+        return App::new().await?.start(issue, base).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("transition") {
+        let issue = sub_matches.get_one::<String>("issue").map(String::as_str);
+        let status = sub_matches.get_one::<String>("status").map(String::as_str);
+        let comment = sub_matches.get_one::<String>("comment").map(String::as_str);
+        return App::new().await?.transition(issue, status, comment).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("create") {
+        let values = |id: &str| -> Vec<String> { sub_matches.get_many::<String>(id).unwrap_or_default().cloned().collect() };
+        let issue = NewIssue {
+            project: sub_matches.get_one::<String>("project").cloned().unwrap_or_default(),
+            summary: sub_matches.get_one::<String>("summary").cloned().unwrap_or_default(),
+            description: sub_matches.get_one::<String>("description").cloned(),
+            labels: values("label"),
+            components: values("component"),
+            priority: sub_matches.get_one::<String>("priority").cloned(),
+            parent: sub_matches.get_one::<String>("parent").cloned(),
+        };
+        let issue_type = sub_matches.get_one::<String>("type").map(String::as_str);
+        return App::new().await?.create(issue, issue_type, sub_matches.get_flag("edit"), sub_matches.get_flag("start")).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("sync-history") {
+        let range = sub_matches.get_one::<String>("range").expect("range is required");
+        let mode = SyncMode::parse(sub_matches.get_one::<String>("mode").expect("has default"))?;
+        let concurrency = *sub_matches.get_one::<usize>("concurrency").expect("has default");
+        let checkpoint = sub_matches.get_one::<PathBuf>("checkpoint").cloned();
+        return App::new().await?
+            .sync_history(range, mode, sub_matches.get_flag("dry_run"), concurrency, checkpoint)
+            .await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("release-notes") {
+        let range = sub_matches.get_one::<String>("range").expect("range is required");
+        let format = sub_matches.get_one::<String>("format").map(|f| NotesFormat::parse(f)).transpose()?;
+        let group_by = sub_matches.get_one::<String>("group_by").map(|g| GroupBy::parse(g)).transpose()?;
+        let output = sub_matches.get_one::<PathBuf>("output");
+        return App::new().await?
+            .release_notes(range, format, group_by, output.map(PathBuf::as_path))
+            .await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("changelog") {
+        if let Some(update_matches) = sub_matches.subcommand_matches("update") {
+            let version = update_matches.get_one::<String>("version").expect("version is required");
+            let file = update_matches.get_one::<PathBuf>("file").cloned();
+            let date = update_matches.get_one::<String>("date").cloned();
+            return App::new().await?
+                .changelog_update(version, file, date, update_matches.get_flag("dry_run"))
+                .await;
+        }
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("release") {
+        let version = sub_matches.get_one::<String>("version").expect("version is required");
+        let range = sub_matches.get_one::<String>("range").expect("range is required");
+        return App::new().await?
+            .release(
+                version,
+                range,
+                sub_matches.get_one::<String>("project").cloned(),
+                sub_matches.get_one::<String>("date").cloned(),
+                sub_matches.get_flag("released"),
+                sub_matches.get_flag("dry_run"),
+            )
+            .await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("next-version") {
+        return App::new().await?.next_version(sub_matches.get_flag("tag")).await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("audit") {
+        let range = sub_matches.get_one::<String>("range").expect("range is required");
+        let format = AuditFormat::parse(sub_matches.get_one::<String>("format").expect("has default"))?;
+        let fix_version = sub_matches.get_one::<String>("fix_version").cloned();
+        let output = sub_matches.get_one::<PathBuf>("output");
+        let result = App::new().await?
+            .audit(range, format, fix_version, output.map(PathBuf::as_path))
+            .await;
+        // 审计不通过时以非零状态退出，便于在 CI 中拦截
+        if let Err(e @ AppError::AuditFailed(_)) = result {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return result;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("log") {
+        let filter = LogFilter {
+            issue: sub_matches.get_one::<String>("issue").cloned(),
+            status: sub_matches.get_one::<String>("status").cloned(),
+            assignee: sub_matches.get_one::<String>("assignee").cloned(),
+        };
+        let max_count = sub_matches.get_one::<usize>("max_count").expect("has default");
+        let mut args = vec![format!("--max-count={}", max_count)];
+        args.extend(sub_matches.get_many::<String>("args").unwrap_or_default().cloned());
+        return App::new().await?
+            .log(filter, sub_matches.get_flag("graph"), sub_matches.get_flag("no_cache"), &args)
+            .await;
+    }
+
+    if let Some(sub_matches) = matches.subcommand_matches("push") {
+        let args: Vec<String> = sub_matches.get_many::<String>("args").unwrap_or_default().cloned().collect();
+        return App::new().await?.push(&args).await;
+    }
+
+    let options = CommitOptions {
+        issue: matches.get_one::<String>("issue").cloned(),
+        time: matches.get_one::<String>("time").cloned(),
+        time_comment: matches.get_one::<String>("time_comment").cloned(),
+    };
+
     match App::new().await {
-        Ok(mut app) => app.run().await,
+        Ok(mut app) => app.run(&options).await,
         Err(AppError::ConfigMissing) => {
             println!("Configuration is missing or incomplete. Let's set it up!");
             handle_config_command().await
@@ -59,10 +505,31 @@ fn handle_help_command() -> Result<(), AppError> {
     println!("  -h, --help, /?    Show this help message");
     println!("  -c, --config      Configure JIRA Git Helper settings");
     println!("  -r, --reset       Reset all configurations");
+    println!("  -i, --issue <KEY> Commit against this issue (key or browse URL)");
+    println!("  -t, --time <DURATION>  Log work after committing (e.g. 1h30m)");
+    println!("  --time-comment <TEXT>  Worklog comment (defaults to the commit subject)");
+    println!();
+    println!("Commands:");
+    println!("  commit-msg <FILE> Validate a commit message file (git commit-msg hook)");
+    println!("  preview <KEY>     Show the issue summary before and after clean-up");
+    println!("  show [KEY]        Show an issue with its description and recent comments");
+    println!("  list              List issues (--mine, --sprint, --project, --status, --jql, --json)");
+    println!("  start <KEY>       Create a branch for an issue and move it to In Progress");
+    println!("  transition [KEY] [STATUS]  Move an issue to another status");
+    println!("  create            Create an issue (--type, --summary, --edit, --start, ...)");
+    println!("  push [ARGS]       Run git push, then apply the configured transition");
+    println!("  sync-history <RANGE>  Link past commits to their issues (--mode, --dry-run)");
+    println!("  release-notes <RANGE> Generate release notes (--format, --group-by, --output)");
+    println!("  changelog update --version <X.Y.Z>  Add a release section to CHANGELOG.md");
+    println!("  release <VERSION> --range <RANGE>  Add a fix version to the issues of a range");
+    println!("  next-version      Suggest the next semantic version (--tag to create it)");
+    println!("  audit <RANGE>     Check the JIRA references of commits (--format table|json|junit)");
+    println!("  log [ARGS]        git log with issue key, status and summary (--graph, --status, ...)");
     println!();
     println!("Normal Usage:");
     println!("  1. Ensure you're in a Git repository.");
     println!("  2. Your branch name should include a JIRA issue ID (e.g., 'feature/PROJ-123-add-login').");
+    println!("     Otherwise pass --issue, or pick one of your open issues when prompted.");
     println!("  3. Run 'jira_git_helper' without any arguments in your repository.");
     println!("  4. The tool will extract the JIRA ID, fetch the issue title, and guide you through the commit process.");
     println!();
@@ -83,4 +550,15 @@ async fn handle_reset_command() -> Result<(), AppError> {
     app_config::reset_config()?;
     println!("All configurations have been reset.");
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn handle_commit_msg_command(file: &Path) -> Result<(), AppError> {
+    // 作为 git hook 运行时不能进入交互式配置
+    let result = App::check_commit_msg(file).await;
+
+    if let Err(e @ AppError::CommitRejected(_)) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    result
+}
@@ -0,0 +1,242 @@
+use crate::issue::Issue;
+use serde::Deserialize;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Template used when `message_template` is not configured.
+pub const DEFAULT_TEMPLATE: &str = "{keys} {summary} {message}";
+
+/// Default template when the reference is written as a trailer instead.
+pub const DEFAULT_TRAILER_TEMPLATE: &str = "{summary} {message}";
+
+/// An additional trailer written for every referenced issue. The value may
+/// use `{key}` and `{url}` (the issue's browse link).
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExtraTrailer {
+    pub name: String,
+    pub value: String,
+}
+
+/// The `[trailer]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct TrailerSettings {
+    /// Write the JIRA reference as a git trailer instead of a subject prefix.
+    pub enabled: bool,
+    pub name: String,
+    pub extra: Vec<ExtraTrailer>,
+}
+
+impl Default for TrailerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            name: "Jira".to_string(),
+            extra: Vec::new(),
+        }
+    }
+}
+
+/// The `[subject]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SubjectSettings {
+    /// Maximum display width of the subject line; unlimited when not set.
+    pub max_length: Option<usize>,
+    pub ellipsis: String,
+}
+
+impl Default for SubjectSettings {
+    fn default() -> Self {
+        Self {
+            max_length: None,
+            ellipsis: "…".to_string(),
+        }
+    }
+}
+
+/// Renders the commit message template.
+///
+/// Supported placeholders:
+/// - `{keys}`: every key in brackets, e.g. `[PROJ-12][PROJ-15]`
+/// - `{key}`: the first key
+/// - `{summary}`: the issue summaries joined with ` / `
+/// - `{message}`: the additional message entered by the user
+///
+/// and, taken from the first issue: `{type}`, `{status}`, `{priority}`,
+/// `{assignee}`, `{labels}`, `{components}`, `{fix_versions}`, `{parent}`
+/// and `{epic}`.
+pub fn render(template: &str, issues: &[Issue], message: &str) -> String {
+    let summary = issues
+        .iter()
+        .map(|issue| issue.summary.as_str())
+        .collect::<Vec<_>>()
+        .join(" / ");
+    render_with_summary(template, issues, &summary, message)
+}
+
+fn render_with_summary(template: &str, issues: &[Issue], summary: &str, message: &str) -> String {
+    let keys: String = issues.iter().map(|issue| format!("[{}]", issue.key)).collect();
+    let key = issues.first().map(|issue| issue.key.as_str()).unwrap_or_default();
+    let first = issues.first().cloned().unwrap_or_default();
+
+    let rendered = fill_template(
+        template,
+        &[
+            ("keys", &keys),
+            ("key", key),
+            ("type", first.issue_type.as_deref().unwrap_or_default()),
+            ("status", first.status_name()),
+            ("priority", first.priority.as_deref().unwrap_or_default()),
+            ("assignee", first.assignee_name()),
+            ("labels", &first.labels.join(", ")),
+            ("components", &first.components.join(", ")),
+            ("fix_versions", &first.fix_versions.join(", ")),
+            ("parent", first.parent.as_ref().map_or("", |p| p.key.as_str())),
+            ("epic", first.epic.as_deref().unwrap_or_default()),
+            ("summary", summary),
+            ("message", message.trim()),
+        ],
+    );
+
+    // 空占位符会留下多余空格
+    rendered
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Builds the `(name, value)` trailers for the referenced issues, one
+/// reference trailer per issue followed by the configured extra trailers.
+pub fn build_trailers(settings: &TrailerSettings, issues: &[Issue], jira_url: &str) -> Vec<(String, String)> {
+    let mut trailers: Vec<(String, String)> = issues
+        .iter()
+        .map(|issue| (settings.name.clone(), issue.key.clone()))
+        .collect();
+
+    for extra in &settings.extra {
+        for issue in issues {
+            let url = crate::jira::browse_url(jira_url, &issue.key);
+            let value = fill_template(&extra.value, &[("key", &issue.key), ("url", &url)]);
+            trailers.push((extra.name.clone(), value));
+        }
+    }
+
+    trailers
+}
+
+/// Renders the template like [`render`], but keeps the subject line within
+/// `max_length` columns. When it is too long the summary is shortened at a
+/// word boundary, and the full summary and the additional message move to
+/// the body.
+pub fn render_with_limit(template: &str, issues: &[Issue], message: &str, settings: &SubjectSettings) -> String {
+    let full = render(template, issues, message);
+    let max_width = match settings.max_length {
+        Some(max_width) => max_width,
+        None => return full,
+    };
+    if display_width(full.lines().next().unwrap_or_default()) <= max_width {
+        return full;
+    }
+
+    let (subject_template, body_template) = match template.split_once('\n') {
+        Some((subject, body)) => (subject, Some(body)),
+        None => (template, None),
+    };
+
+    // 先计算去掉标题后模板本身占用的宽度
+    let fixed = render_with_summary(subject_template, issues, "", "");
+    let separator = if fixed.is_empty() { 0 } else { 1 };
+    let available = max_width.saturating_sub(display_width(&fixed) + separator);
+
+    let summary = issues.iter().map(|issue| issue.summary.as_str()).collect::<Vec<_>>().join(" / ");
+    let short = truncate_at_word(&summary, available, &settings.ellipsis);
+    let mut subject = render_with_summary(subject_template, issues, &short, "");
+    if display_width(&subject) > max_width {
+        subject = truncate_at_word(&subject, max_width, &settings.ellipsis);
+    }
+
+    let mut body: Vec<String> = issues.iter().map(|issue| issue.summary.clone()).collect();
+    if !message.trim().is_empty() {
+        body.push(String::new());
+        body.push(message.trim().to_string());
+    }
+    if let Some(body_template) = body_template {
+        let rest = render(body_template, issues, message);
+        if !rest.is_empty() {
+            body.push(String::new());
+            body.push(rest);
+        }
+    }
+
+    format!("{}\n\n{}", subject, body.join("\n"))
+}
+
+/// Replaces `{name}` placeholders with their values in a single pass, so
+/// braces inside the values are never expanded again. Unknown placeholders
+/// are kept as they are.
+pub fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let value = placeholder.find('}').and_then(|end| {
+            let name = &placeholder[1..end];
+            values.iter().find(|(n, _)| *n == name).map(|(_, v)| (*v, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Width of the text in terminal columns; CJK characters count as two.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Shortens the text to at most `max_width` columns including the ellipsis,
+/// cutting at the last space or between CJK characters when possible.
+pub fn truncate_at_word(text: &str, max_width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(display_width(ellipsis));
+    let mut width = 0;
+    let mut hard_cut = 0;
+    let mut word_cut = None;
+    for (index, c) in text.char_indices() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+        width += char_width;
+        hard_cut = index + c.len_utf8();
+        if c.is_whitespace() {
+            word_cut = Some(index);
+        } else if char_width > 1 {
+            // 中日韩文字之间没有空格，可以在任意字符后断开
+            word_cut = Some(hard_cut);
+        }
+    }
+
+    let cut = match word_cut {
+        Some(cut) if cut > 0 => cut,
+        _ => hard_cut,
+    };
+    let kept = text[..cut].trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | '.' | ';' | ':' | '-' | '，' | '。' | '；' | '：'));
+    format!("{}{}", kept, ellipsis)
+}
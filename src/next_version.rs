@@ -0,0 +1,199 @@
+use crate::issue::Issue;
+use semver::Version;
+use serde::Deserialize;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::fmt;
+
+/// Part of the version to increase.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn apply(self, version: &Version) -> Version {
+        match self {
+            Bump::Major => Version::new(version.major + 1, 0, 0),
+            Bump::Minor => Version::new(version.major, version.minor + 1, 0),
+            Bump::Patch => Version::new(version.major, version.minor, version.patch + 1),
+        }
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        })
+    }
+}
+
+/// Bumps the version when an issue has one of the types or labels, or a
+/// value in the custom field.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BumpRule {
+    pub bump: Bump,
+    #[serde(default)]
+    pub issue_types: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Custom field ID, e.g. `customfield_10100`.
+    pub field: Option<String>,
+    /// Values of `field` that match; any value matches when empty.
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+impl BumpRule {
+    /// Why the rule matches the issue, or `None`.
+    fn reason(&self, issue: &Issue) -> Option<String> {
+        if let Some(issue_type) = issue.issue_type.as_ref().filter(|t| contains(&self.issue_types, t)) {
+            return Some(format!("type {}", issue_type));
+        }
+        if let Some(label) = issue.labels.iter().find(|l| contains(&self.labels, l)) {
+            return Some(format!("label {}", label));
+        }
+        let field = self.field.as_ref()?;
+        let values = field_values(issue.custom_fields.get(field)?);
+        let value = if self.values.is_empty() {
+            values.into_iter().next()?
+        } else {
+            values.into_iter().find(|v| contains(&self.values, v))?
+        };
+        Some(format!("{} = {}", field, value))
+    }
+}
+
+/// The `[next_version]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NextVersionSettings {
+    /// Prefix of version tags.
+    pub tag_prefix: String,
+    /// Checked in order; the first matching rule decides the bump of an issue.
+    pub rules: Vec<BumpRule>,
+    /// Bump of issues no rule matches and of commits without issues.
+    pub default_bump: Bump,
+}
+
+impl Default for NextVersionSettings {
+    fn default() -> Self {
+        let rule = |bump, issue_types: &[&str], labels: &[&str]| BumpRule {
+            bump,
+            issue_types: issue_types.iter().map(|s| s.to_string()).collect(),
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            field: None,
+            values: Vec::new(),
+        };
+        Self {
+            tag_prefix: "v".to_string(),
+            rules: vec![
+                rule(Bump::Major, &[], &["breaking", "breaking-change"]),
+                rule(Bump::Minor, &["Story", "New Feature", "Epic"], &[]),
+            ],
+            default_bump: Bump::Patch,
+        }
+    }
+}
+
+impl NextVersionSettings {
+    /// Custom fields the rules need from JIRA.
+    pub fn fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = self.rules.iter().filter_map(|r| r.field.clone()).collect();
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+
+    pub fn tag(&self, version: &Version) -> String {
+        format!("{}{}", self.tag_prefix, version)
+    }
+}
+
+/// The highest tag that is a stable semantic version after the prefix;
+/// prerelease tags such as `v2.0.0-rc.1` are ignored.
+pub fn latest_version(tags: &[String], prefix: &str) -> Option<(String, Version)> {
+    tags.iter()
+        .filter_map(|tag| {
+            let version = Version::parse(tag.strip_prefix(prefix)?).ok()?;
+            Some((tag.clone(), version))
+        })
+        .filter(|(_, version)| version.pre.is_empty())
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// The bump an issue calls for and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reason {
+    pub bump: Bump,
+    pub subject: String,
+    pub why: String,
+}
+
+/// Suggested bump from the issues and the commits without issues.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub bump: Bump,
+    pub next: Version,
+    pub reasons: Vec<Reason>,
+}
+
+pub fn suggest(current: &Version, issues: &[Issue], unkeyed_commits: usize, settings: &NextVersionSettings) -> Suggestion {
+    let mut reasons: Vec<Reason> = issues
+        .iter()
+        .map(|issue| {
+            let subject = format!("{} {}", issue.key, issue.summary);
+            match settings.rules.iter().find_map(|r| r.reason(issue).map(|why| (r.bump, why))) {
+                Some((bump, why)) => Reason { bump, subject, why },
+                None => Reason {
+                    bump: settings.default_bump,
+                    subject,
+                    why: "default".to_string(),
+                },
+            }
+        })
+        .collect();
+    if unkeyed_commits > 0 {
+        reasons.push(Reason {
+            bump: settings.default_bump,
+            subject: format!("{} commit(s) without an issue", unkeyed_commits),
+            why: "default".to_string(),
+        });
+    }
+    reasons.sort_by_key(|r| Reverse(r.bump));
+
+    let bump = reasons.first().map_or(settings.default_bump, |r| r.bump);
+    Suggestion {
+        bump,
+        next: bump.apply(current),
+        reasons,
+    }
+}
+
+fn contains(list: &[String], value: &str) -> bool {
+    list.iter().any(|item| item.eq_ignore_ascii_case(value))
+}
+
+/// Text values of a custom field: strings, numbers, options (`value`/`name`)
+/// and arrays of them.
+fn field_values(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => vec![s.clone()],
+        Value::Number(n) => vec![n.to_string()],
+        Value::Bool(true) => vec!["true".to_string()],
+        Value::Object(map) => map
+            .get("value")
+            .or_else(|| map.get("name"))
+            .and_then(Value::as_str)
+            .map(|s| vec![s.to_string()])
+            .unwrap_or_default(),
+        Value::Array(items) => items.iter().flat_map(field_values).collect(),
+        _ => Vec::new(),
+    }
+}
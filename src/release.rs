@@ -0,0 +1,120 @@
+use crate::issue::Issue;
+use serde::Deserialize;
+use std::fmt;
+
+/// The `[release]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ReleaseSettings {
+    /// Project of the fix version when `--project` is not given.
+    pub project: Option<String>,
+    /// Name of the JIRA version, e.g. `App {version}`.
+    pub version_name: String,
+}
+
+impl Default for ReleaseSettings {
+    fn default() -> Self {
+        Self {
+            project: None,
+            version_name: "{version}".to_string(),
+        }
+    }
+}
+
+/// What `release` does with an issue referenced in the range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueAction {
+    AddVersion,
+    AlreadyTagged,
+    /// Skipped, the issue already has these fix versions.
+    OtherVersion(Vec<String>),
+    /// Skipped, the version belongs to another project.
+    OtherProject,
+}
+
+/// Decides for every issue whether the fix version is added. Issues that
+/// already have another fix version are left alone, so the report can point
+/// out work that was planned for a different release.
+pub fn plan(issues: &[Issue], project: &str, version: &str) -> Vec<(String, IssueAction)> {
+    issues
+        .iter()
+        .map(|issue| {
+            let issue_project = issue.key.rsplit_once('-').map_or("", |(project, _)| project);
+            let action = if !issue_project.eq_ignore_ascii_case(project) {
+                IssueAction::OtherProject
+            } else if issue.fix_versions.iter().any(|v| v == version) {
+                IssueAction::AlreadyTagged
+            } else if !issue.fix_versions.is_empty() {
+                IssueAction::OtherVersion(issue.fix_versions.clone())
+            } else {
+                IssueAction::AddVersion
+            };
+            (issue.key.clone(), action)
+        })
+        .collect()
+}
+
+/// Outcome of a `release` run.
+#[derive(Debug, Default)]
+pub struct ReleaseReport {
+    pub version: String,
+    pub dry_run: bool,
+    pub created: bool,
+    pub released: bool,
+    pub added: Vec<String>,
+    pub already_tagged: Vec<String>,
+    pub other_version: Vec<(String, Vec<String>)>,
+    pub other_project: Vec<String>,
+    /// Issues whose update failed, with the error.
+    pub failures: Vec<(String, String)>,
+}
+
+impl ReleaseReport {
+    pub fn record(&mut self, key: String, action: IssueAction) {
+        match action {
+            IssueAction::AddVersion => self.added.push(key),
+            IssueAction::AlreadyTagged => self.already_tagged.push(key),
+            IssueAction::OtherVersion(versions) => self.other_version.push((key, versions)),
+            IssueAction::OtherProject => self.other_project.push(key),
+        }
+    }
+}
+
+impl fmt::Display for ReleaseReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (create, add, mark) = if self.dry_run {
+            ("Would create", "Would add", "Would mark")
+        } else {
+            ("Created", "Added", "Marked")
+        };
+        if self.created {
+            writeln!(f, "{} version {}", create, self.version)?;
+        }
+        write!(f, "{} {} to {} issue(s)", add, self.version, self.added.len())?;
+        if !self.added.is_empty() {
+            write!(f, ": {}", self.added.join(", "))?;
+        }
+        if !self.already_tagged.is_empty() {
+            write!(f, "\nAlready on {}: {}", self.version, self.already_tagged.join(", "))?;
+        }
+        if !self.other_version.is_empty() {
+            write!(f, "\nSkipped, already on another version:")?;
+            for (key, versions) in &self.other_version {
+                write!(f, "\n  {} ({})", key, versions.join(", "))?;
+            }
+        }
+        if !self.other_project.is_empty() {
+            write!(f, "\nSkipped, other project: {}", self.other_project.join(", "))?;
+        }
+        if !self.failures.is_empty() {
+            write!(f, "\nFailed:")?;
+            for (key, error) in &self.failures {
+                write!(f, "\n  {}: {}", key, error)?;
+            }
+        }
+        if self.released {
+            write!(f, "\n{} {} as released", mark, self.version)?;
+        }
+        Ok(())
+    }
+}
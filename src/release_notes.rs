@@ -0,0 +1,295 @@
+use crate::git::CommitInfo;
+use crate::issue::Issue;
+use crate::jira::browse_url;
+use crate::message::fill_template;
+use crate::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Output formats of `release-notes`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotesFormat {
+    Markdown,
+    Html,
+    Text,
+    Json,
+    /// The layout from `[release_notes.template]`.
+    Template,
+}
+
+impl NotesFormat {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value {
+            "markdown" | "md" => Ok(NotesFormat::Markdown),
+            "html" => Ok(NotesFormat::Html),
+            "text" => Ok(NotesFormat::Text),
+            "json" => Ok(NotesFormat::Json),
+            "template" => Ok(NotesFormat::Template),
+            other => Err(AppError::Other(format!(
+                "Unknown format '{}', expected markdown, html, text, json or template",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    Type,
+    Component,
+}
+
+impl GroupBy {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        match value {
+            "type" => Ok(GroupBy::Type),
+            "component" => Ok(GroupBy::Component),
+            other => Err(AppError::Other(format!("Unknown grouping '{}', expected type or component", other))),
+        }
+    }
+}
+
+/// A custom layout, used with the `template` format. Defaults produce Markdown.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NotesTemplate {
+    /// Supports `{title}`.
+    pub header: String,
+    /// Supports `{group}` and `{count}`.
+    pub group: String,
+    /// Supports `{key}`, `{summary}`, `{url}`, `{type}`, `{status}` and `{components}`.
+    pub item: String,
+    pub commits_heading: String,
+    /// Supports `{hash}`, `{short_hash}`, `{subject}` and `{author}`.
+    pub commit: String,
+}
+
+impl Default for NotesTemplate {
+    fn default() -> Self {
+        Self {
+            header: "# {title}\n".to_string(),
+            group: "\n## {group}\n".to_string(),
+            item: "- [{key}]({url}) {summary}".to_string(),
+            commits_heading: "\n## Other changes\n".to_string(),
+            commit: "- {short_hash} {subject}".to_string(),
+        }
+    }
+}
+
+/// The `[release_notes]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct ReleaseNotesSettings {
+    /// Supports `{range}`.
+    pub title: String,
+    pub format: NotesFormat,
+    pub group_by: GroupBy,
+    pub template: NotesTemplate,
+}
+
+impl Default for ReleaseNotesSettings {
+    fn default() -> Self {
+        Self {
+            title: "Release notes {range}".to_string(),
+            format: NotesFormat::Markdown,
+            group_by: GroupBy::Type,
+            template: NotesTemplate::default(),
+        }
+    }
+}
+
+/// Name of the group for issues without a type or component.
+const UNGROUPED: &str = "Other";
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReleaseNotes {
+    pub title: String,
+    pub groups: Vec<NotesGroup>,
+    /// Commits in the range that mention no issue.
+    pub commits_without_issues: Vec<NotesCommit>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NotesGroup {
+    pub name: String,
+    pub issues: Vec<NotesIssue>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NotesIssue {
+    pub key: String,
+    pub summary: String,
+    pub url: String,
+    pub issue_type: String,
+    pub status: String,
+    pub components: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct NotesCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub subject: String,
+    pub author: String,
+}
+
+impl ReleaseNotes {
+    /// Groups the issues by type or component, sorted by group name with the
+    /// ungrouped issues last. An issue with several components is listed
+    /// under each of them.
+    pub fn build(title: &str, issues: &[Issue], unkeyed: &[CommitInfo], group_by: GroupBy, jira_url: &str) -> Self {
+        let mut groups: BTreeMap<String, Vec<NotesIssue>> = BTreeMap::new();
+        for issue in issues {
+            let names = match group_by {
+                GroupBy::Type => issue.issue_type.clone().into_iter().collect(),
+                GroupBy::Component => issue.components.clone(),
+            };
+            let names = if names.is_empty() { vec![UNGROUPED.to_string()] } else { names };
+            for name in names {
+                groups.entry(name).or_default().push(NotesIssue {
+                    key: issue.key.clone(),
+                    summary: issue.summary.clone(),
+                    url: browse_url(jira_url, &issue.key),
+                    issue_type: issue.issue_type.clone().unwrap_or_default(),
+                    status: issue.status_name().to_string(),
+                    components: issue.components.clone(),
+                });
+            }
+        }
+
+        let mut groups: Vec<NotesGroup> = groups
+            .into_iter()
+            .map(|(name, mut issues)| {
+                issues.sort_by_key(|i| issue_sort_key(&i.key));
+                NotesGroup { name, issues }
+            })
+            .collect();
+        groups.sort_by_key(|g| g.name == UNGROUPED);
+
+        ReleaseNotes {
+            title: title.to_string(),
+            groups,
+            commits_without_issues: unkeyed
+                .iter()
+                .map(|c| NotesCommit {
+                    hash: c.hash.clone(),
+                    short_hash: c.short_hash.clone(),
+                    subject: c.subject.clone(),
+                    author: c.author.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn render(&self, format: NotesFormat, template: &NotesTemplate) -> String {
+        match format {
+            NotesFormat::Markdown => self.render_template(&NotesTemplate::default()),
+            NotesFormat::Template => self.render_template(template),
+            NotesFormat::Text => self.render_text(),
+            NotesFormat::Html => self.render_html(),
+            NotesFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+        }
+    }
+
+    fn render_template(&self, template: &NotesTemplate) -> String {
+        let mut out = fill_template(&template.header, &[("title", &self.title)]);
+        for group in &self.groups {
+            let count = group.issues.len().to_string();
+            out.push_str(&fill_template(&template.group, &[("group", &group.name), ("count", &count)]));
+            for issue in &group.issues {
+                let line = fill_template(
+                    &template.item,
+                    &[
+                        ("key", &issue.key),
+                        ("summary", &issue.summary),
+                        ("url", &issue.url),
+                        ("type", &issue.issue_type),
+                        ("status", &issue.status),
+                        ("components", &issue.components.join(", ")),
+                    ],
+                );
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        if !self.commits_without_issues.is_empty() {
+            out.push_str(&template.commits_heading);
+            for commit in &self.commits_without_issues {
+                let line = fill_template(
+                    &template.commit,
+                    &[
+                        ("hash", &commit.hash),
+                        ("short_hash", &commit.short_hash),
+                        ("subject", &commit.subject),
+                        ("author", &commit.author),
+                    ],
+                );
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    fn render_text(&self) -> String {
+        let mut out = format!("{}\n{}\n", self.title, "=".repeat(self.title.chars().count()));
+        for group in &self.groups {
+            out.push_str(&format!("\n{}\n", group.name));
+            for issue in &group.issues {
+                out.push_str(&format!("  * {} {} ({})\n", issue.key, issue.summary, issue.url));
+            }
+        }
+        if !self.commits_without_issues.is_empty() {
+            out.push_str("\nOther changes\n");
+            for commit in &self.commits_without_issues {
+                out.push_str(&format!("  * {} {}\n", commit.short_hash, commit.subject));
+            }
+        }
+        out
+    }
+
+    fn render_html(&self) -> String {
+        let mut out = format!("<h1>{}</h1>\n", escape_html(&self.title));
+        for group in &self.groups {
+            out.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape_html(&group.name)));
+            for issue in &group.issues {
+                out.push_str(&format!(
+                    "  <li><a href=\"{}\">{}</a> {}</li>\n",
+                    escape_html(&issue.url),
+                    escape_html(&issue.key),
+                    escape_html(&issue.summary)
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+        if !self.commits_without_issues.is_empty() {
+            out.push_str("<h2>Other changes</h2>\n<ul>\n");
+            for commit in &self.commits_without_issues {
+                out.push_str(&format!(
+                    "  <li><code>{}</code> {}</li>\n",
+                    escape_html(&commit.short_hash),
+                    escape_html(&commit.subject)
+                ));
+            }
+            out.push_str("</ul>\n");
+        }
+        out
+    }
+}
+
+/// Sorts `PROJ-9` before `PROJ-10`.
+fn issue_sort_key(key: &str) -> (String, u64) {
+    match key.rsplit_once('-') {
+        Some((project, number)) => (project.to_string(), number.parse().unwrap_or(0)),
+        None => (key.to_string(), 0),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,118 @@
+use serde::Deserialize;
+
+/// Git hosting services whose web URLs are known.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    Gitea,
+}
+
+impl Provider {
+    /// Guesses the provider from the host name.
+    pub fn detect(host: &str) -> Option<Provider> {
+        let host = host.to_ascii_lowercase();
+        if host.contains("github") {
+            Some(Provider::GitHub)
+        } else if host.contains("gitlab") {
+            Some(Provider::GitLab)
+        } else if host.contains("bitbucket") {
+            Some(Provider::Bitbucket)
+        } else if host.contains("gitea") || host.contains("codeberg") {
+            Some(Provider::Gitea)
+        } else {
+            None
+        }
+    }
+}
+
+/// A repository on a git hosting service, parsed from a remote URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    /// Web scheme: `http` for plain HTTP remotes, `https` otherwise.
+    pub scheme: String,
+    /// Host name, with the port for HTTP(S) remotes.
+    pub host: String,
+    /// Repository path without `.git`, e.g. `owner/repo`.
+    pub path: String,
+}
+
+impl RemoteRepo {
+    /// Parses the SCP-like (`git@host:owner/repo.git`), `ssh://`, `git://`
+    /// and `http(s)://` forms of a remote URL.
+    pub fn parse(url: &str) -> Option<RemoteRepo> {
+        let url = url.trim();
+        let mut web_scheme = "https";
+        let (host, path) = match url.split_once("://") {
+            Some((scheme, rest)) => {
+                let (authority, path) = rest.split_once('/')?;
+                let host = authority.rsplit('@').next()?;
+                // SSH 端口不是网页端口
+                let host = match scheme {
+                    "http" | "https" => {
+                        web_scheme = scheme;
+                        host
+                    }
+                    _ => host.split(':').next()?,
+                };
+                (host, path)
+            }
+            None => {
+                let (authority, path) = url.split_once(':')?;
+                (authority.rsplit('@').next()?, path)
+            }
+        };
+
+        let path = path.trim_matches('/').trim_end_matches(".git").trim_end_matches('/');
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+        Some(RemoteRepo {
+            scheme: web_scheme.to_string(),
+            host: host.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Web page of the repository.
+    pub fn web_url(&self) -> String {
+        match self.bitbucket_server_path() {
+            Some((project, repo)) => format!("{}://{}/projects/{}/repos/{}", self.scheme, self.host, project.to_uppercase(), repo),
+            None => format!("{}://{}/{}", self.scheme, self.host, self.path),
+        }
+    }
+
+    /// Web page of a commit, or `None` when the provider is unknown.
+    pub fn commit_url(&self, provider: Option<Provider>, hash: &str) -> Option<String> {
+        let provider = provider.or_else(|| Provider::detect(&self.host))?;
+        let url = match provider {
+            Provider::GitHub | Provider::Gitea => format!("{}/commit/{}", self.web_url(), hash),
+            Provider::GitLab => format!("{}/-/commit/{}", self.web_url(), hash),
+            Provider::Bitbucket => format!("{}/commits/{}", self.web_url(), hash),
+        };
+        Some(url)
+    }
+
+    /// Web page of a branch, or `None` when the provider is unknown.
+    pub fn branch_url(&self, provider: Option<Provider>, branch: &str) -> Option<String> {
+        let provider = provider.or_else(|| Provider::detect(&self.host))?;
+        let url = match provider {
+            Provider::GitHub => format!("{}/tree/{}", self.web_url(), branch),
+            Provider::GitLab => format!("{}/-/tree/{}", self.web_url(), branch),
+            Provider::Bitbucket if self.bitbucket_server_path().is_some() => {
+                format!("{}/browse?at=refs/heads/{}", self.web_url(), branch)
+            }
+            Provider::Bitbucket => format!("{}/branch/{}", self.web_url(), branch),
+            Provider::Gitea => format!("{}/src/branch/{}", self.web_url(), branch),
+        };
+        Some(url)
+    }
+
+    /// Project and repository of Bitbucket Server clone URLs (`scm/PROJ/repo`).
+    fn bitbucket_server_path(&self) -> Option<(&str, &str)> {
+        let rest = self.path.strip_prefix("scm/")?;
+        rest.split_once('/')
+    }
+}
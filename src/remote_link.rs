@@ -0,0 +1,76 @@
+use crate::git::CommitInfo;
+use crate::jira::RemoteLink;
+use crate::message::fill_template;
+use crate::remote::{Provider, RemoteRepo};
+use serde::Deserialize;
+
+/// The `[remote_link]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RemoteLinkSettings {
+    /// Link pushed commits and branches from their issues.
+    pub enabled: bool,
+    /// Add a link for every pushed commit.
+    pub commit: bool,
+    /// Add a link for the pushed branch.
+    pub branch: bool,
+    pub remote: String,
+    /// Hosting service of the remote; guessed from the host name when not set.
+    pub provider: Option<Provider>,
+    /// Commit URL format, overriding the provider's. Supports `{repo_url}`,
+    /// `{host}`, `{path}`, `{hash}` and `{short_hash}`.
+    pub commit_url: Option<String>,
+    /// Branch URL format, overriding the provider's. Supports `{repo_url}`,
+    /// `{host}`, `{path}` and `{branch}`.
+    pub branch_url: Option<String>,
+}
+
+impl Default for RemoteLinkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            commit: true,
+            branch: false,
+            remote: "origin".to_string(),
+            provider: None,
+            commit_url: None,
+            branch_url: None,
+        }
+    }
+}
+
+/// The link to a commit, or `None` when its URL cannot be built.
+pub fn commit_link(repo: &RemoteRepo, settings: &RemoteLinkSettings, commit: &CommitInfo) -> Option<RemoteLink> {
+    let url = match &settings.commit_url {
+        Some(format) => fill_url(format, repo, &[("hash", &commit.hash), ("short_hash", &commit.short_hash)]),
+        None => repo.commit_url(settings.provider, &commit.hash)?,
+    };
+    Some(RemoteLink {
+        global_id: format!("git-commit={}@{}", repo.web_url(), commit.hash),
+        url,
+        title: format!("{} {}", commit.short_hash, commit.subject),
+        summary: format!("Commit by {} in {}", commit.author, repo.path),
+    })
+}
+
+/// The link to a branch, or `None` when its URL cannot be built.
+pub fn branch_link(repo: &RemoteRepo, settings: &RemoteLinkSettings, branch: &str) -> Option<RemoteLink> {
+    let url = match &settings.branch_url {
+        Some(format) => fill_url(format, repo, &[("branch", branch)]),
+        None => repo.branch_url(settings.provider, branch)?,
+    };
+    Some(RemoteLink {
+        global_id: format!("git-branch={}@{}", repo.web_url(), branch),
+        url,
+        title: format!("Branch {}", branch),
+        summary: format!("Branch in {}", repo.path),
+    })
+}
+
+/// Fills the repository placeholders and the link's own `values` in one pass.
+fn fill_url(format: &str, repo: &RemoteRepo, values: &[(&str, &str)]) -> String {
+    let repo_url = repo.web_url();
+    let mut all = vec![("repo_url", repo_url.as_str()), ("host", repo.host.as_str()), ("path", repo.path.as_str())];
+    all.extend_from_slice(values);
+    fill_template(format, &all)
+}
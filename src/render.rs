@@ -0,0 +1,454 @@
+use crate::issue::{Comment, Issue, RichText};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde_json::Value;
+use std::io::IsTerminal;
+use unicode_width::UnicodeWidthChar;
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const ITALIC: &str = "\x1b[3m";
+const UNDERLINE: &str = "\x1b[4m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Prefix of code block lines; such lines are never wrapped.
+const CODE_PREFIX: &str = "  │ ";
+
+/// Renders issues and JIRA rich text (wiki markup or ADF) as terminal text.
+pub struct Renderer {
+    color: bool,
+    width: usize,
+}
+
+impl Renderer {
+    pub fn new(color: bool, width: usize) -> Self {
+        Self { color, width: width.max(20) }
+    }
+
+    /// Uses colors when stdout is a terminal and `NO_COLOR` is not set, and
+    /// wraps to `$COLUMNS` (default 80).
+    pub fn detect() -> Self {
+        let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        let width = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()).unwrap_or(80);
+        Self::new(color, width)
+    }
+
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.color && !text.is_empty() {
+            format!("{}{}{}", style, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn link(&self, text: &str, url: &str) -> String {
+        if text.is_empty() || text == url {
+            self.paint(UNDERLINE, url)
+        } else {
+            format!("{} ({})", text, self.paint(UNDERLINE, url))
+        }
+    }
+
+    /// Renders the issue header, details, description and comments.
+    pub fn issue(&self, issue: &Issue, url: &str, comments: &[Comment]) -> String {
+        let mut out = Vec::new();
+        out.push(format!("{}  {}", self.paint(BOLD, &issue.key), self.paint(BOLD, &issue.summary)));
+
+        let status = issue.status.as_ref().map(|status| {
+            let color = match status.category.as_ref().map(|c| c.key.as_str()) {
+                Some("done") => GREEN,
+                Some("indeterminate") => YELLOW,
+                _ => BLUE,
+            };
+            self.paint(color, &status.name)
+        });
+        let summary_line: Vec<String> = [issue.issue_type.clone(), status, issue.priority.clone()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if !summary_line.is_empty() {
+            out.push(summary_line.join(" · "));
+        }
+
+        let mut details = vec![
+            ("Assignee", issue.assignee.as_ref().map(|u| u.display_name.clone()).unwrap_or_else(|| "Unassigned".to_string())),
+            ("Reporter", issue.reporter.as_ref().map(|u| u.display_name.clone()).unwrap_or_default()),
+            ("Labels", issue.labels.join(", ")),
+            ("Components", issue.components.join(", ")),
+            ("Fix versions", issue.fix_versions.join(", ")),
+            ("Parent", issue.parent.as_ref().map(|p| format!("{} {}", p.key, p.summary)).unwrap_or_default()),
+            ("Epic", issue.epic.clone().unwrap_or_default()),
+        ];
+        details.retain(|(_, value)| !value.is_empty());
+        out.push(String::new());
+        for (label, value) in details {
+            out.push(format!("{} {}", self.paint(DIM, &format!("{:<13}", format!("{}:", label))), value));
+        }
+        for subtask in &issue.subtasks {
+            out.push(format!("{} {} {}", self.paint(DIM, &format!("{:<13}", "Subtask:")), subtask.key, subtask.summary));
+        }
+        out.push(format!("{} {}", self.paint(DIM, &format!("{:<13}", "URL:")), self.paint(UNDERLINE, url)));
+
+        out.push(String::new());
+        out.push(self.paint(BOLD, "Description"));
+        match &issue.description {
+            Some(description) => out.push(self.rich_text(description)),
+            None => out.push(self.paint(DIM, "No description")),
+        }
+
+        if !comments.is_empty() {
+            out.push(String::new());
+            out.push(self.paint(BOLD, &format!("Comments ({})", comments.len())));
+            for comment in comments {
+                let author = comment.author.as_ref().map_or("Unknown", |a| a.display_name.as_str());
+                let created = comment.created.get(..16).unwrap_or(&comment.created).replace('T', " ");
+                out.push(String::new());
+                out.push(format!("{} {}", self.paint(CYAN, author), self.paint(DIM, &created)));
+                out.push(self.rich_text(&comment.body));
+            }
+        }
+
+        out.join("\n")
+    }
+
+    /// Converts wiki markup or ADF to wrapped terminal text.
+    pub fn rich_text(&self, text: &RichText) -> String {
+        let converted = match text {
+            RichText::Wiki(wiki) => self.wiki(wiki),
+            RichText::Adf(adf) => self.adf(adf),
+        };
+        self.wrap(&converted)
+    }
+
+    /// Converts JIRA wiki markup (API v2) to terminal text.
+    pub fn wiki(&self, markup: &str) -> String {
+        lazy_static! {
+            static ref HEADING: Regex = Regex::new(r"^h[1-6]\.\s+(.*)$").unwrap();
+            static ref LIST: Regex = Regex::new(r"^([*#-]+)\s+(.*)$").unwrap();
+            static ref CODE: Regex = Regex::new(r"^\{(code|noformat)(:[^}]*)?\}(.*)$").unwrap();
+        }
+
+        let mut out = Vec::new();
+        let mut in_code = false;
+        let mut in_quote = false;
+        let mut counters: Vec<usize> = Vec::new();
+
+        for line in markup.replace("\r\n", "\n").lines() {
+            let trimmed = line.trim();
+
+            if in_code {
+                if let Some(end) = trimmed.strip_suffix("{code}").or_else(|| trimmed.strip_suffix("{noformat}")) {
+                    if !end.is_empty() {
+                        out.push(format!("{}{}", CODE_PREFIX, self.paint(DIM, end)));
+                    }
+                    in_code = false;
+                } else {
+                    out.push(format!("{}{}", CODE_PREFIX, self.paint(DIM, line)));
+                }
+                continue;
+            }
+            if let Some(caps) = CODE.captures(trimmed) {
+                in_code = true;
+                let rest = caps[3].trim();
+                if !rest.is_empty() {
+                    let content = rest.trim_end_matches("{code}").trim_end_matches("{noformat}");
+                    out.push(format!("{}{}", CODE_PREFIX, self.paint(DIM, content)));
+                    in_code = content.len() == rest.len();
+                }
+                continue;
+            }
+            if trimmed == "{quote}" {
+                in_quote = !in_quote;
+                continue;
+            }
+
+            let rendered = if let Some(caps) = HEADING.captures(trimmed) {
+                counters.clear();
+                self.paint(BOLD, &self.wiki_inline(&caps[1]))
+            } else if let Some(caps) = LIST.captures(trimmed).filter(|c| c[1].len() <= 6) {
+                let marker = &caps[1];
+                let depth = marker.len();
+                counters.resize(depth, 0);
+                let indent = "  ".repeat(depth - 1);
+                let bullet = if marker.ends_with('#') {
+                    counters[depth - 1] += 1;
+                    format!("{}.", counters[depth - 1])
+                } else {
+                    "•".to_string()
+                };
+                format!("{}{} {}", indent, bullet, self.wiki_inline(&caps[2]))
+            } else if trimmed == "----" {
+                counters.clear();
+                self.paint(DIM, &"─".repeat(self.width.min(40)))
+            } else if let Some(quote) = trimmed.strip_prefix("bq. ") {
+                format!("> {}", self.paint(ITALIC, &self.wiki_inline(quote)))
+            } else if trimmed.starts_with('|') {
+                self.wiki_table_row(trimmed)
+            } else {
+                counters.clear();
+                self.wiki_inline(trimmed)
+            };
+
+            if in_quote {
+                out.push(format!("> {}", rendered));
+            } else {
+                out.push(rendered);
+            }
+        }
+
+        out.join("\n")
+    }
+
+    fn wiki_table_row(&self, row: &str) -> String {
+        let header = row.starts_with("||");
+        let separator = if header { "||" } else { "|" };
+        let cells: Vec<String> = row
+            .trim_matches('|')
+            .split(separator)
+            .map(|cell| self.wiki_inline(cell.trim()))
+            .collect();
+        let joined = cells.join(" | ");
+        if header {
+            self.paint(BOLD, &joined)
+        } else {
+            joined
+        }
+    }
+
+    fn wiki_inline(&self, text: &str) -> String {
+        lazy_static! {
+            static ref LINK: Regex = Regex::new(r"\[([^|\]\[]*)\|([^\]\[]+)\]").unwrap();
+            static ref BARE_LINK: Regex = Regex::new(r"\[((?:https?|mailto):[^\]\[]+)\]").unwrap();
+            static ref MENTION: Regex = Regex::new(r"\[~([^\]]+)\]").unwrap();
+            static ref MONO: Regex = Regex::new(r"\{\{(.+?)\}\}").unwrap();
+            static ref BOLD_RE: Regex = Regex::new(r"(^|[\s(])\*([^*\s](?:[^*]*[^*\s])?)\*").unwrap();
+            static ref ITALIC_RE: Regex = Regex::new(r"(^|[\s(])_([^_\s](?:[^_]*[^_\s])?)_").unwrap();
+            static ref IMAGE: Regex = Regex::new(r"!([^!\s|]+)(\|[^!]*)?!").unwrap();
+            static ref MACRO: Regex = Regex::new(r"\{(color|panel)(:[^}]*)?\}").unwrap();
+        }
+
+        let text = MACRO.replace_all(text, "");
+        let text = MONO.replace_all(&text, |c: &Captures| self.paint(CYAN, &c[1]));
+        let text = IMAGE.replace_all(&text, |c: &Captures| self.paint(DIM, &format!("[image: {}]", &c[1])));
+        let text = MENTION.replace_all(&text, |c: &Captures| self.paint(CYAN, &format!("@{}", &c[1])));
+        let text = LINK.replace_all(&text, |c: &Captures| self.link(&c[1], &c[2]));
+        let text = BARE_LINK.replace_all(&text, |c: &Captures| self.link("", &c[1]));
+        let text = BOLD_RE.replace_all(&text, |c: &Captures| format!("{}{}", &c[1], self.paint(BOLD, &c[2])));
+        let text = ITALIC_RE.replace_all(&text, |c: &Captures| format!("{}{}", &c[1], self.paint(ITALIC, &c[2])));
+        text.into_owned()
+    }
+
+    /// Converts an Atlassian Document Format tree (API v3) to terminal text.
+    pub fn adf(&self, doc: &Value) -> String {
+        let mut out = Vec::new();
+        self.adf_blocks(doc, 0, &mut out);
+        out.join("\n")
+    }
+
+    fn adf_blocks(&self, node: &Value, depth: usize, out: &mut Vec<String>) {
+        let indent = "  ".repeat(depth);
+        let children = node["content"].as_array().map(Vec::as_slice).unwrap_or_default();
+
+        match node["type"].as_str().unwrap_or_default() {
+            "doc" | "panel" | "layoutSection" | "layoutColumn" | "expand" => {
+                for child in children {
+                    self.adf_blocks(child, depth, out);
+                }
+            }
+            "paragraph" => {
+                for line in self.adf_inline(children).lines() {
+                    out.push(format!("{}{}", indent, line));
+                }
+                if children.is_empty() {
+                    out.push(String::new());
+                }
+            }
+            "heading" => out.push(format!("{}{}", indent, self.paint(BOLD, &self.adf_inline(children)))),
+            "bulletList" | "orderedList" => {
+                let ordered = node["type"] == "orderedList";
+                let start = node["attrs"]["order"].as_u64().unwrap_or(1);
+                for (i, item) in children.iter().enumerate() {
+                    let bullet = if ordered { format!("{}.", start + i as u64) } else { "•".to_string() };
+                    let mut item_lines = Vec::new();
+                    self.adf_blocks(item, 0, &mut item_lines);
+                    for (j, line) in item_lines.into_iter().enumerate() {
+                        if j == 0 {
+                            out.push(format!("{}{} {}", indent, bullet, line));
+                        } else {
+                            out.push(format!("{}  {}", indent, line));
+                        }
+                    }
+                }
+            }
+            "listItem" | "tableCell" | "tableHeader" => {
+                for child in children {
+                    self.adf_blocks(child, depth, out);
+                }
+            }
+            "codeBlock" => {
+                let code: String = children.iter().filter_map(|c| c["text"].as_str()).collect();
+                for line in code.lines() {
+                    out.push(format!("{}{}", CODE_PREFIX, self.paint(DIM, line)));
+                }
+            }
+            "blockquote" => {
+                let mut quoted = Vec::new();
+                for child in children {
+                    self.adf_blocks(child, 0, &mut quoted);
+                }
+                out.extend(quoted.into_iter().map(|line| format!("{}> {}", indent, line)));
+            }
+            "rule" => out.push(self.paint(DIM, &"─".repeat(self.width.min(40)))),
+            "table" => {
+                for row in children {
+                    let cells: Vec<String> = row["content"]
+                        .as_array()
+                        .map(Vec::as_slice)
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|cell| {
+                            let mut lines = Vec::new();
+                            self.adf_blocks(cell, 0, &mut lines);
+                            let text = lines.join(" ");
+                            if cell["type"] == "tableHeader" {
+                                self.paint(BOLD, &text)
+                            } else {
+                                text
+                            }
+                        })
+                        .collect();
+                    out.push(format!("{}{}", indent, cells.join(" | ")));
+                }
+            }
+            "mediaSingle" | "mediaGroup" => out.push(format!("{}{}", indent, self.paint(DIM, "[attachment]"))),
+            _ => {
+                let text = self.adf_inline(std::slice::from_ref(node));
+                if !text.is_empty() {
+                    out.push(format!("{}{}", indent, text));
+                }
+            }
+        }
+    }
+
+    fn adf_inline(&self, nodes: &[Value]) -> String {
+        let mut text = String::new();
+        for node in nodes {
+            match node["type"].as_str().unwrap_or_default() {
+                "text" => {
+                    let mut span = node["text"].as_str().unwrap_or_default().to_string();
+                    for mark in node["marks"].as_array().map(Vec::as_slice).unwrap_or_default() {
+                        span = match mark["type"].as_str().unwrap_or_default() {
+                            "strong" => self.paint(BOLD, &span),
+                            "em" => self.paint(ITALIC, &span),
+                            "code" => self.paint(CYAN, &span),
+                            "link" => self.link(&span, mark["attrs"]["href"].as_str().unwrap_or_default()),
+                            _ => span,
+                        };
+                    }
+                    text.push_str(&span);
+                }
+                "hardBreak" => text.push('\n'),
+                "mention" => text.push_str(&self.paint(CYAN, node["attrs"]["text"].as_str().unwrap_or("@user"))),
+                "emoji" => text.push_str(node["attrs"]["text"].as_str().or(node["attrs"]["shortName"].as_str()).unwrap_or_default()),
+                "inlineCard" | "blockCard" => text.push_str(&self.link("", node["attrs"]["url"].as_str().unwrap_or_default())),
+                "status" => text.push_str(&format!("[{}]", node["attrs"]["text"].as_str().unwrap_or_default())),
+                "date" => text.push_str(node["attrs"]["timestamp"].as_str().unwrap_or_default()),
+                _ => text.push_str(&self.adf_inline(node["content"].as_array().map(Vec::as_slice).unwrap_or_default())),
+            }
+        }
+        text
+    }
+
+    /// Wraps lines to the terminal width, keeping the indentation of list
+    /// items and quotes on continuation lines. Code lines are left as is.
+    pub fn wrap(&self, text: &str) -> String {
+        let mut out = Vec::new();
+        for line in text.lines() {
+            if line.starts_with(CODE_PREFIX) || visible_width(line) <= self.width {
+                out.push(line.to_string());
+                continue;
+            }
+
+            let hang = hanging_indent(line);
+            let mut current = String::new();
+            let mut current_width = 0;
+            for word in line.split(' ') {
+                let word_width = visible_width(word);
+                if current_width > 0 && current_width + 1 + word_width > self.width {
+                    out.push(std::mem::take(&mut current));
+                    current.push_str(&" ".repeat(hang));
+                    current_width = hang;
+                } else if current_width > 0 {
+                    current.push(' ');
+                    current_width += 1;
+                }
+                // 单个词超出宽度（如长段中文）时按字符断开
+                for c in split_visible(word) {
+                    let w = visible_width(&c);
+                    if current_width + w > self.width && current_width > hang {
+                        out.push(std::mem::take(&mut current));
+                        current.push_str(&" ".repeat(hang));
+                        current_width = hang;
+                    }
+                    current.push_str(&c);
+                    current_width += w;
+                }
+            }
+            out.push(current);
+        }
+        out.join("\n")
+    }
+}
+
+/// Width of the text without ANSI escape sequences.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            width += c.width().unwrap_or(0);
+        }
+    }
+    width
+}
+
+/// Splits text into visible characters, attaching ANSI escape sequences to
+/// the following character so they are never broken apart.
+fn split_visible(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut pending = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            pending.push(c);
+            for next in chars.by_ref() {
+                pending.push(next);
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        pending.push(c);
+        parts.push(std::mem::take(&mut pending));
+    }
+    if !pending.is_empty() {
+        parts.push(pending);
+    }
+    parts
+}
+
+fn hanging_indent(line: &str) -> usize {
+    lazy_static! {
+        static ref PREFIX: Regex = Regex::new(r"^(\s*(?:> )*(?:•|\d+\.)?\s*)").unwrap();
+    }
+    PREFIX.find(line).map_or(0, |m| visible_width(m.as_str()))
+}
@@ -0,0 +1,109 @@
+use crate::AppError;
+use regex::Regex;
+use serde::Deserialize;
+
+/// One step of the summary pipeline, configured as `[[summary.steps]]`
+/// tables with a `type` field.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Transform {
+    /// Removes every match of the regex, e.g. bracketed tags like `[Backend]`.
+    Strip { pattern: String },
+    /// Replaces every match of the regex; `$1` style references are supported.
+    Replace { pattern: String, replacement: String },
+    RemoveEmoji,
+    /// Converts full-width characters and CJK punctuation to their ASCII forms.
+    NormalizePunctuation,
+    /// Collapses runs of whitespace into a single space and trims the ends.
+    NormalizeWhitespace,
+    /// Removes trailing periods and exclamation marks.
+    TrimTrailingPunctuation,
+}
+
+/// The `[summary]` section of the configuration file. Steps run in order.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SummarySettings {
+    pub steps: Vec<Transform>,
+}
+
+enum CompiledStep {
+    Replace(Regex, String),
+    RemoveEmoji,
+    NormalizePunctuation,
+    NormalizeWhitespace,
+    TrimTrailingPunctuation,
+}
+
+/// Cleans JIRA summaries before they land in the commit message.
+pub struct Sanitizer {
+    steps: Vec<CompiledStep>,
+}
+
+impl Sanitizer {
+    pub fn new(settings: &SummarySettings) -> Result<Self, AppError> {
+        let compile = |pattern: &str| {
+            Regex::new(pattern).map_err(|e| AppError::ConfigString(format!("Invalid summary pattern '{}': {}", pattern, e)))
+        };
+
+        let steps = settings
+            .steps
+            .iter()
+            .map(|step| {
+                Ok(match step {
+                    Transform::Strip { pattern } => CompiledStep::Replace(compile(pattern)?, String::new()),
+                    Transform::Replace { pattern, replacement } => CompiledStep::Replace(compile(pattern)?, replacement.clone()),
+                    Transform::RemoveEmoji => CompiledStep::RemoveEmoji,
+                    Transform::NormalizePunctuation => CompiledStep::NormalizePunctuation,
+                    Transform::NormalizeWhitespace => CompiledStep::NormalizeWhitespace,
+                    Transform::TrimTrailingPunctuation => CompiledStep::TrimTrailingPunctuation,
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        Ok(Self { steps })
+    }
+
+    pub fn apply(&self, summary: &str) -> String {
+        let mut summary = summary.to_string();
+        for step in &self.steps {
+            summary = match step {
+                CompiledStep::Replace(regex, replacement) => regex.replace_all(&summary, replacement.as_str()).into_owned(),
+                CompiledStep::RemoveEmoji => summary.chars().filter(|c| !is_emoji(*c)).collect(),
+                CompiledStep::NormalizePunctuation => summary.chars().map(to_half_width).collect(),
+                CompiledStep::NormalizeWhitespace => summary.split_whitespace().collect::<Vec<_>>().join(" "),
+                CompiledStep::TrimTrailingPunctuation => summary
+                    .trim_end_matches(|c: char| c.is_whitespace() || matches!(c, '.' | '。' | '．' | '!' | '！'))
+                    .to_string(),
+            };
+        }
+        summary
+    }
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF   // 表情、符号、旗帜
+        | 0x2600..=0x27BF   // 杂项符号和装饰符号
+        | 0x2B00..=0x2BFF   // 箭头和星形
+        | 0xFE0E..=0xFE0F   // 变体选择符
+        | 0x200D            // 零宽连接符
+        | 0x20E3            // 组合用键帽
+        | 0xE0020..=0xE007F // 标签字符
+    )
+}
+
+fn to_half_width(c: char) -> char {
+    match c {
+        '\u{3000}' => ' ',
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '、' => ',',
+        '。' => '.',
+        '【' | '〔' => '[',
+        '】' | '〕' => ']',
+        '「' | '」' | '『' | '』' | '“' | '”' => '"',
+        '‘' | '’' => '\'',
+        '—' => '-',
+        _ => c,
+    }
+}
@@ -0,0 +1,110 @@
+use deunicode::deunicode_char;
+
+/// Turns an issue summary into a branch-name slug: Chinese is transliterated
+/// to pinyin (one word per character), other scripts are transliterated
+/// where possible and dropped otherwise, and the result is lowercased ASCII
+/// joined with `-`, cut at a word boundary to at most `max_length` bytes. A
+/// word that ends exactly at the limit is kept.
+pub fn slugify(text: &str, max_length: usize) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            current.push(c.to_ascii_lowercase());
+            continue;
+        }
+        if c.is_ascii() {
+            flush(&mut current, &mut words);
+            continue;
+        }
+
+        // 汉字转写为带空格的拼音（如 "Xiu "），按空格拆成单独的词
+        let transliterated = deunicode_char(c).unwrap_or_default();
+        let is_syllable = transliterated.ends_with(' ');
+        if is_syllable {
+            flush(&mut current, &mut words);
+        }
+        for t in transliterated.chars() {
+            if t.is_ascii_alphanumeric() {
+                current.push(t.to_ascii_lowercase());
+            } else {
+                flush(&mut current, &mut words);
+            }
+        }
+        if is_syllable {
+            flush(&mut current, &mut words);
+        }
+    }
+    flush(&mut current, &mut words);
+
+    let mut slug = String::new();
+    for word in words {
+        let extra = if slug.is_empty() { word.len() } else { word.len() + 1 };
+        if slug.len() + extra > max_length {
+            if slug.is_empty() {
+                // 第一个词就超长时只能硬截断
+                slug.push_str(&word[..max_length.min(word.len())]);
+            }
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word);
+    }
+    slug
+}
+
+fn flush(current: &mut String, words: &mut Vec<String>) {
+    if !current.is_empty() {
+        words.push(std::mem::take(current));
+    }
+}
+
+/// Rewrites a branch name so it passes `git check-ref-format --branch`:
+/// forbidden characters become `-`, empty and dot-leading components are
+/// removed, and `..`, `@{`, `.lock` suffixes and trailing dots are avoided.
+/// Names that would be empty, `@` or start with `-` get a `branch` prefix.
+pub fn sanitize_ref_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || c.is_whitespace() || matches!(c, '~' | '^' | ':' | '?' | '*' | '[' | '\\') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let mut replaced = replaced.replace("@{", "-");
+    while replaced.contains("..") {
+        replaced = replaced.replace("..", ".");
+    }
+
+    let components: Vec<String> = replaced
+        .split('/')
+        .map(|component| {
+            let mut component = component.trim_start_matches('.').to_string();
+            while component.ends_with(".lock") || component.ends_with('.') {
+                component = component
+                    .strip_suffix(".lock")
+                    .unwrap_or(&component)
+                    .trim_end_matches('.')
+                    .to_string();
+            }
+            component
+        })
+        .filter(|component| !component.is_empty())
+        .collect();
+
+    let name = components.join("/");
+    let rest = name.trim_start_matches('-');
+    if rest.is_empty() || rest == "@" {
+        "branch".to_string()
+    } else if rest.len() < name.len() {
+        format!("branch-{}", rest)
+    } else {
+        name
+    }
+}
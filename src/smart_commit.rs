@@ -0,0 +1,108 @@
+use crate::worklog::normalize_duration;
+use crate::AppError;
+use serde::Deserialize;
+
+/// The `[smart_commits]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct SmartCommitSettings {
+    /// Run Smart Commit commands from the commit message through the REST API.
+    pub enabled: bool,
+    /// Remove the commands from the commit message instead of keeping them.
+    pub strip: bool,
+}
+
+/// A Smart Commit command, executed for every referenced issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmartCommand {
+    /// `#comment <text>`
+    Comment(String),
+    /// `#time <duration> [comment]`, with the duration in JIRA syntax.
+    Time { time_spent: String, comment: String },
+    /// `#<transition-name> [comment]`, e.g. `#resolve` or `#start-progress`.
+    Transition { name: String, comment: String },
+}
+
+impl SmartCommand {
+    /// Short form used in reports, e.g. `#time 2h`.
+    pub fn label(&self) -> String {
+        match self {
+            SmartCommand::Comment(_) => "#comment".to_string(),
+            SmartCommand::Time { time_spent, .. } => format!("#time {}", time_spent),
+            SmartCommand::Transition { name, .. } => format!("#{}", name.replace(' ', "-")),
+        }
+    }
+}
+
+/// A message split into the text before the first command and the commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SmartCommit {
+    pub message: String,
+    pub commands: Vec<SmartCommand>,
+}
+
+/// Parses Smart Commit commands. A command is a `#` followed by a name
+/// starting with a letter, at the start of the text or after whitespace;
+/// its arguments run until the next command. Hyphens in transition names
+/// stand for spaces. `#time` durations are validated here so mistakes are
+/// caught before committing.
+pub fn parse(text: &str) -> Result<SmartCommit, AppError> {
+    let starts = command_starts(text);
+    let Some(&first) = starts.first() else {
+        return Ok(SmartCommit {
+            message: text.trim().to_string(),
+            commands: Vec::new(),
+        });
+    };
+
+    let mut commands = Vec::new();
+    for (i, &start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied().unwrap_or(text.len());
+        let command = &text[start + 1..end];
+        let (name, args) = command.split_once(char::is_whitespace).unwrap_or((command, ""));
+        let args = args.trim().to_string();
+
+        commands.push(match name.to_ascii_lowercase().as_str() {
+            "comment" => SmartCommand::Comment(args),
+            "time" => {
+                let (time_spent, comment) = split_duration(&args)?;
+                SmartCommand::Time { time_spent, comment }
+            }
+            _ => SmartCommand::Transition {
+                name: name.replace('-', " "),
+                comment: args,
+            },
+        });
+    }
+
+    Ok(SmartCommit {
+        message: text[..first].trim().to_string(),
+        commands,
+    })
+}
+
+/// Byte offsets of the `#` of every command.
+fn command_starts(text: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut previous: Option<char> = None;
+    for (i, c) in text.char_indices() {
+        let at_boundary = previous.is_none_or(char::is_whitespace);
+        let next_is_letter = text[i + c.len_utf8()..].chars().next().is_some_and(|n| n.is_ascii_alphabetic());
+        if c == '#' && at_boundary && next_is_letter {
+            starts.push(i);
+        }
+        previous = Some(c);
+    }
+    starts
+}
+
+/// Splits `#time` arguments into the leading duration and the comment.
+fn split_duration(args: &str) -> Result<(String, String), AppError> {
+    let words: Vec<&str> = args.split_whitespace().collect();
+    let count = words
+        .iter()
+        .take_while(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .count();
+    let time_spent = normalize_duration(&words[..count].join(" "))?;
+    Ok((time_spent, words[count..].join(" ")))
+}
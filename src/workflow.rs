@@ -0,0 +1,81 @@
+use crate::branch::find_transition;
+use crate::jira::{AllowedValue, Transition, FieldMeta};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Field ID of the comment on transition screens; it is sent as an update
+/// instead of a field value.
+pub const COMMENT_FIELD: &str = "comment";
+
+/// The `[auto_transition]` section of the configuration file: statuses the
+/// issues are moved to when something happens in git.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct AutoTransitionSettings {
+    /// After the first commit on a branch made with this tool.
+    pub first_commit: Option<String>,
+    /// After a successful `jira_git_helper push`.
+    pub push: Option<String>,
+}
+
+/// Label of a transition in pickers, e.g. `Start Progress → In Progress`.
+pub fn transition_label(transition: &Transition) -> String {
+    if transition.name.eq_ignore_ascii_case(&transition.to.name) {
+        transition.name.clone()
+    } else {
+        format!("{} → {}", transition.name, transition.to.name)
+    }
+}
+
+/// Fields of the transition screen that must be filled in: required, without
+/// a default value, and not the comment.
+pub fn required_fields(transition: &Transition) -> Vec<(&str, &FieldMeta)> {
+    transition
+        .fields
+        .iter()
+        .filter(|(id, field)| field.required && !field.has_default_value && id.as_str() != COMMENT_FIELD)
+        .map(|(id, field)| (id.as_str(), field))
+        .collect()
+}
+
+/// Whether the transition screen has a comment field.
+pub fn has_comment_field(transition: &Transition) -> bool {
+    transition.fields.contains_key(COMMENT_FIELD)
+}
+
+/// Text shown for an allowed value in pickers.
+pub fn allowed_value_label(value: &AllowedValue) -> String {
+    value
+        .name
+        .clone()
+        .or_else(|| value.value.clone())
+        .or_else(|| value.id.clone())
+        .unwrap_or_default()
+}
+
+/// The value sent for a field when the allowed value was chosen: by ID when
+/// known, otherwise by name or value.
+pub fn allowed_value_json(value: &AllowedValue) -> Value {
+    match (&value.id, &value.name, &value.value) {
+        (Some(id), _, _) => json!({ "id": id }),
+        (None, Some(name), _) => json!({ "name": name }),
+        (None, None, Some(value)) => json!({ "value": value }),
+        (None, None, None) => Value::Null,
+    }
+}
+
+/// Finds the transition for a Smart Commit `#<transition-name>` command:
+/// an exact transition or status name, or else the only transition whose
+/// name starts with it, so `#resolve` matches `Resolve Issue`.
+pub fn find_command_transition<'a>(transitions: &'a [Transition], name: &str) -> Option<&'a Transition> {
+    if let Some(transition) = find_transition(transitions, name) {
+        return Some(transition);
+    }
+
+    let name = name.to_lowercase();
+    let mut matches = transitions.iter().filter(|t| t.name.to_lowercase().starts_with(&name));
+    match (matches.next(), matches.next()) {
+        (Some(transition), None) => Some(transition),
+        _ => None,
+    }
+}
@@ -0,0 +1,64 @@
+use crate::AppError;
+use serde::Deserialize;
+
+/// The `[worklog]` section of the configuration file.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct WorklogSettings {
+    /// Ask for the time spent on every commit when `--time` is not given.
+    pub prompt: bool,
+}
+
+/// Validates a JIRA duration such as `1h30m`, `2d 4h` or `1.5h` and returns
+/// it in the form JIRA expects for `timeSpent`, e.g. `1h 30m`.
+///
+/// Each of the units `w`, `d`, `h` and `m` may be used once, largest first.
+pub fn normalize_duration(input: &str) -> Result<String, AppError> {
+    let invalid = |reason: &str| AppError::Other(format!("Invalid duration '{}': {}", input.trim(), reason));
+    const UNITS: [char; 4] = ['w', 'd', 'h', 'm'];
+
+    let mut parts: Vec<String> = Vec::new();
+    let mut last_unit: Option<usize> = None;
+    let mut total = 0.0;
+    let mut chars = input.trim().chars().peekable();
+
+    while chars.peek().is_some() {
+        let mut number = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_ascii_digit() || c == '.' {
+                number.push(c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        let unit = chars.next().map(|c| c.to_ascii_lowercase());
+
+        if number.is_empty() {
+            return Err(invalid("expected a number, e.g. 1h30m"));
+        }
+        let value: f64 = number.parse().map_err(|_| invalid("malformed number"))?;
+        let index = match unit.and_then(|u| UNITS.iter().position(|&c| c == u)) {
+            Some(index) => index,
+            None => return Err(invalid("every number needs a unit: w, d, h or m")),
+        };
+        if last_unit.is_some_and(|last| index <= last) {
+            return Err(invalid("units must be given once, from weeks down to minutes"));
+        }
+        last_unit = Some(index);
+        total += value;
+        parts.push(format!("{}{}", number, UNITS[index]));
+
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+    }
+
+    if parts.is_empty() {
+        return Err(invalid("empty"));
+    }
+    if total <= 0.0 {
+        return Err(invalid("must be greater than zero"));
+    }
+    Ok(parts.join(" "))
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use jira_git_helper::audit::{audit, commit_keys, AuditFormat, AuditSettings, ProblemKind};
+use jira_git_helper::git::CommitInfo;
+use jira_git_helper::issue::{Issue, IssueStatus, StatusCategory};
+use jira_git_helper::issue_key::KeyExtractionSettings;
+use std::collections::HashMap;
+
+use common::{commit, issue, strings};
+
+fn status(category: &str) -> Option<IssueStatus> {
+    Some(IssueStatus {
+        name: if category == "done" { "Closed" } else { "In Progress" }.to_string(),
+        category: Some(StatusCategory {
+            key: category.to_string(),
+            name: category.to_string(),
+        }),
+    })
+}
+
+fn sample() -> (Vec<CommitInfo>, Vec<Vec<String>>, HashMap<String, Issue>) {
+    let commits = vec![
+        commit("aaa1", "[PROJ-1] Add login"),
+        commit("bbb2", "Fix typo"),
+        commit("ccc3", "Bump version to 1.2.0"),
+        commit("ddd4", "[OPS-1] Update pipeline"),
+        commit("eee5", "[PROJ-9] Unknown issue"),
+        commit("fff6", "[PROJ-2] Closed & planned for 1.1.0"),
+    ];
+    let commit_keys = vec![strings(&["PROJ-1"]), strings(&[]), strings(&[]), strings(&["OPS-1"]), strings(&["PROJ-9"]), strings(&["PROJ-2", "PROJ-3"])];
+    let issues = [
+        Issue { status: status("indeterminate"), fix_versions: strings(&["1.2.0"]), ..issue("PROJ-1", "", &[]) },
+        Issue { status: status("done"), ..issue("PROJ-2", "", &[]) },
+        Issue { status: status("new"), fix_versions: strings(&["1.1.0"]), ..issue("PROJ-3", "", &[]) },
+    ]
+    .into_iter()
+        .map(|i| (i.key.clone(), i))
+        .collect();
+    (commits, commit_keys, issues)
+}
+
+#[test]
+fn test_audit_problems() {
+    let (commits, commit_keys, issues) = sample();
+    let settings = AuditSettings {
+        projects: vec!["PROJ".to_string()],
+        ignore_subjects: vec!["^Bump version".to_string()],
+        ..AuditSettings::default()
+    };
+
+    let report = audit("v1.1.0..HEAD", &commits, &commit_keys, &issues, Some("1.2.0"), &settings).unwrap();
+    let kinds: Vec<(&str, Vec<ProblemKind>)> = report
+        .failed()
+        .map(|c| (c.short_hash.as_str(), c.problems.iter().map(|p| p.kind).collect()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("bbb2", vec![ProblemKind::MissingKey]),
+            ("ddd4", vec![ProblemKind::UnexpectedProject]),
+            ("eee5", vec![ProblemKind::UnknownIssue]),
+            ("fff6", vec![ProblemKind::ClosedIssue, ProblemKind::OtherFixVersion]),
+        ]
+    );
+
+    let lenient = AuditSettings {
+        allow_closed: true,
+        ..AuditSettings::default()
+    };
+    let report = audit("v1.1.0..HEAD", &commits, &commit_keys, &issues, None, &lenient).unwrap();
+    assert_eq!(report.failed().count(), 4);
+}
+
+#[test]
+fn test_keys_of_other_projects_are_unexpected() {
+    let commits = vec![
+        CommitInfo { message: "[OPS-12] Update pipeline\n\nRefs PROJ-3".to_string(), ..commit("aaa1", "[OPS-12] Update pipeline") },
+        CommitInfo { message: "Fix typo".to_string(), ..commit("bbb2", "Fix typo") },
+    ];
+    let key_settings = KeyExtractionSettings {
+        projects: strings(&["PROJ"]),
+        ..KeyExtractionSettings::default()
+    };
+    let commit_keys = commit_keys(&commits, &key_settings).unwrap();
+    assert_eq!(commit_keys, vec![strings(&["OPS-12", "PROJ-3"]), strings(&[])]);
+
+    let mut settings = AuditSettings::default();
+    settings.fill_projects(&key_settings.projects, Some("CORE"));
+    assert_eq!(settings.projects, strings(&["PROJ"]));
+    let report = audit("main..HEAD", &commits, &commit_keys, &HashMap::new(), None, &settings).unwrap();
+    let kinds: Vec<(&str, Vec<ProblemKind>)> = report
+        .failed()
+        .map(|c| (c.short_hash.as_str(), c.problems.iter().map(|p| p.kind).collect()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("aaa1", vec![ProblemKind::UnexpectedProject, ProblemKind::UnknownIssue]),
+            ("bbb2", vec![ProblemKind::MissingKey]),
+        ]
+    );
+
+    let mut fallback = AuditSettings::default();
+    fallback.fill_projects(&[], Some("CORE"));
+    assert_eq!(fallback.projects, strings(&["CORE"]));
+}
+
+#[test]
+fn test_render_formats() {
+    let (commits, commit_keys, issues) = sample();
+    let report = audit("v1.1.0..HEAD", &commits[4..], &commit_keys[4..], &issues, Some("1.2.0"), &AuditSettings::default()).unwrap();
+
+    let table = report.render(AuditFormat::Table);
+    assert!(table.starts_with("COMMIT  SUBJECT                              PROBLEM            DETAIL\n"));
+    assert!(table.contains("eee5    [PROJ-9] Unknown issue               unknown issue      PROJ-9 does not exist or is not visible\n"));
+    assert!(table.ends_with("2 of 2 commits in v1.1.0..HEAD have problems\n"));
+
+    let json: serde_json::Value = serde_json::from_str(&report.render(AuditFormat::Json)).unwrap();
+    assert_eq!(json["commits"][1]["problems"][1]["kind"], "other_fix_version");
+
+    let junit = report.render(AuditFormat::Junit);
+    assert!(junit.contains("<testsuite name=\"jira-audit v1.1.0..HEAD\" tests=\"2\" failures=\"2\">"));
+    assert!(junit.contains("<testcase classname=\"audit\" name=\"fff6 [PROJ-2] Closed &amp; planned for 1.1.0\">"));
+    assert!(junit.contains("<failure type=\"closed issue\" message=\"PROJ-2 is Closed\"/>"));
+}
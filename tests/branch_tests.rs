@@ -0,0 +1,30 @@
+use jira_git_helper::branch::{branch_name, find_transition, StartSettings};
+use jira_git_helper::issue::{Issue, IssueStatus};
+use jira_git_helper::jira::Transition;
+
+#[test]
+fn test_branch_name_from_pattern() {
+    let settings = StartSettings::default();
+    let bug = Issue { issue_type: Some("Bug".to_string()), ..Issue::new("PROJ-12", "Fix login crash!") };
+    assert_eq!(branch_name(&bug, &settings), "bugfix/PROJ-12-fix-login-crash");
+
+    let story = Issue { issue_type: Some("Story".to_string()), ..Issue::new("PROJ-3", "") };
+    assert_eq!(branch_name(&story, &settings), "feature/PROJ-3");
+
+    let custom = StartSettings { branch_pattern: "{project}/{number}_{slug}".to_string(), ..StartSettings::default() };
+    assert_eq!(branch_name(&story, &custom), "PROJ/3");
+}
+
+#[test]
+fn test_find_transition() {
+    let transition = |id: &str, name: &str, to: &str| Transition {
+        id: id.to_string(),
+        name: name.to_string(),
+        to: IssueStatus { name: to.to_string(), category: None },
+        fields: Default::default(),
+    };
+    let transitions = vec![transition("11", "Start Progress", "In Progress"), transition("21", "Done", "Closed")];
+    assert_eq!(find_transition(&transitions, "in progress").unwrap().id, "11");
+    assert_eq!(find_transition(&transitions, "Done").unwrap().id, "21");
+    assert!(find_transition(&transitions, "Review").is_none());
+}
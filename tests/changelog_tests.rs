@@ -0,0 +1,143 @@
+use jira_git_helper::changelog::{entries, last_version, update, ChangelogSettings, Entry};
+use jira_git_helper::issue::Issue;
+
+fn entry(section: &str, key: &str, text: &str) -> Entry {
+    Entry {
+        section: section.to_string(),
+        key: key.to_string(),
+        text: text.to_string(),
+    }
+}
+
+#[test]
+fn test_entries_map_types_and_labels() {
+    let bug = Issue {
+        issue_type: Some("Bug".to_string()),
+        ..Issue::new("PROJ-1", "Fix crash ")
+    };
+    let removed = Issue {
+        issue_type: Some("Story".to_string()),
+        labels: vec!["Removed".to_string()],
+        ..Issue::new("PROJ-2", "Drop XML export")
+    };
+    let task = Issue {
+        issue_type: Some("Task".to_string()),
+        ..Issue::new("PROJ-3", "Upgrade deps")
+    };
+
+    let result = entries(&[bug, removed, task], &ChangelogSettings::default(), "https://jira.example.com");
+    let sections: Vec<&str> = result.iter().map(|e| e.section.as_str()).collect();
+    assert_eq!(sections, vec!["Fixed", "Removed", "Changed"]);
+    assert_eq!(result[0].text, "Fix crash ([PROJ-1](https://jira.example.com/browse/PROJ-1))");
+}
+
+#[test]
+fn test_update_moves_unreleased_entries() {
+    let content = "\
+# Changelog
+
+## [Unreleased]
+
+Some notes.
+
+### Fixed
+
+- Hand-written fix for PROJ-2
+
+## [1.0.0] - 2024-01-01
+
+### Added
+
+- First release
+
+[1.0.0]: https://example.com/v1.0.0
+";
+    assert_eq!(last_version(content).as_deref(), Some("1.0.0"));
+
+    let generated = vec![
+        entry("Fixed", "PROJ-1", "Fix crash"),
+        entry("Fixed", "PROJ-2", "Duplicate of the hand-written entry"),
+        entry("Added", "PROJ-12", "New login"),
+    ];
+    let updated = update(content, "1.1.0", "v1.1.0", "2024-02-01", &generated).unwrap();
+    let expected = "\
+# Changelog
+
+## [Unreleased]
+
+Some notes.
+
+## [1.1.0] - 2024-02-01
+
+### Added
+
+- New login
+
+### Fixed
+
+- Hand-written fix for PROJ-2
+- Fix crash
+
+## [1.0.0] - 2024-01-01
+
+### Added
+
+- First release
+
+[1.0.0]: https://example.com/v1.0.0
+";
+    assert_eq!(updated.content, expected);
+    assert!(updated.section.starts_with("## [1.1.0] - 2024-02-01\n"));
+
+    assert!(update(&updated.content, "1.1.0", "v1.1.0", "2024-02-02", &generated).is_err());
+}
+
+#[test]
+fn test_update_without_unreleased_section() {
+    let content = "# Changelog\n\nIntro.\n\n## 0.9.0\n\n- Old\n";
+    assert_eq!(last_version(content).as_deref(), Some("0.9.0"));
+
+    let updated = update(content, "1.0.0", "v1.0.0", "2024-01-01", &[entry("Changed", "PROJ-1", "Tweak")]).unwrap();
+    assert_eq!(
+        updated.content,
+        "# Changelog\n\nIntro.\n\n## [1.0.0] - 2024-01-01\n\n### Changed\n\n- Tweak\n\n## 0.9.0\n\n- Old\n"
+    );
+}
+
+#[test]
+fn test_update_creates_new_file() {
+    let updated = update("", "0.1.0", "v0.1.0", "2024-01-01", &[entry("Added", "PROJ-1", "Login")]).unwrap();
+    assert!(updated.content.starts_with("# Changelog\n"));
+    assert!(updated.content.contains("## [Unreleased]\n\n## [0.1.0] - 2024-01-01\n\n### Added\n\n- Login\n"));
+    assert_eq!(last_version(&updated.content).as_deref(), Some("0.1.0"));
+}
+
+#[test]
+fn test_update_compare_links() {
+    let content = "\
+## [Unreleased]
+
+- Tweak PROJ-1
+
+## [1.0.0] - 2024-01-01
+
+- First release
+
+[Unreleased]: https://github.com/owner/repo/compare/v1.0.0...HEAD
+[1.0.0]: https://github.com/owner/repo/compare/v0.9.0...v1.0.0
+";
+    let updated = update(content, "1.1.0", "v1.1.0", "2024-02-01", &[]).unwrap();
+    assert!(updated.content.ends_with(
+        "\
+[Unreleased]: https://github.com/owner/repo/compare/v1.1.0...HEAD
+[1.1.0]: https://github.com/owner/repo/compare/v1.0.0...v1.1.0
+[1.0.0]: https://github.com/owner/repo/compare/v0.9.0...v1.0.0
+"
+    ));
+
+    let without_unreleased = "## [1.0.0] - 2024-01-01\n\n- First release\n\n[1.0.0]: https://github.com/owner/repo/compare/v0.9.0...v1.0.0\n";
+    let updated = update(without_unreleased, "1.1.0", "v1.1.0", "2024-02-01", &[]).unwrap();
+    assert!(updated.content.ends_with(
+        "[1.1.0]: https://github.com/owner/repo/compare/v1.0.0...v1.1.0\n[1.0.0]: https://github.com/owner/repo/compare/v0.9.0...v1.0.0\n"
+    ));
+}
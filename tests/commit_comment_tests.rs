@@ -0,0 +1,43 @@
+use jira_git_helper::commit_comment::{render_comment, DEFAULT_COMMENT_TEMPLATE};
+use jira_git_helper::git::CommitInfo;
+
+fn commit() -> CommitInfo {
+    CommitInfo {
+        hash: "abc123def456".to_string(),
+        short_hash: "abc123d".to_string(),
+        subject: "[PROJ-12] Fix login_form crash".to_string(),
+        author: "Li Lei".to_string(),
+        committed_at: "2024-03-01T18:30:00.000+0800".to_string(),
+        ..CommitInfo::default()
+    }
+}
+
+#[test]
+fn test_render_comment_escapes_wiki_markup() {
+    let url = "https://github.com/owner/repo/commit/abc123def456";
+    assert_eq!(
+        render_comment(DEFAULT_COMMENT_TEMPLATE, &commit(), "bugfix/PROJ-12", Some(url), true),
+        "Li Lei committed abc123d on branch bugfix/PROJ-12:\n\\[PROJ-12\\] Fix login\\_form crash\nhttps://github.com/owner/repo/commit/abc123def456"
+    );
+    assert_eq!(
+        render_comment("{subject}", &commit(), "main", None, false),
+        "[PROJ-12] Fix login_form crash"
+    );
+}
+
+#[test]
+fn test_render_comment_drops_empty_lines() {
+    assert_eq!(
+        render_comment("{short_hash} {branch}\n{url}\n\nDone", &commit(), "main", None, false),
+        "abc123d main\n\nDone"
+    );
+}
+
+#[test]
+fn test_render_comment_keeps_braces_in_values() {
+    let commit = CommitInfo { subject: "Log {branch} and {url}".to_string(), ..commit() };
+    assert_eq!(
+        render_comment("{subject} on {branch}", &commit, "main", Some("https://example.com"), false),
+        "Log {branch} and {url} on main"
+    );
+}
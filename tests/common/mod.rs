@@ -0,0 +1,28 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use jira_git_helper::git::CommitInfo;
+use jira_git_helper::issue::Issue;
+
+pub fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+/// A commit with the short hash, a full hash derived from it and the subject.
+pub fn commit(short_hash: &str, subject: &str) -> CommitInfo {
+    CommitInfo {
+        hash: format!("{}0000", short_hash),
+        short_hash: short_hash.to_string(),
+        subject: subject.to_string(),
+        ..CommitInfo::default()
+    }
+}
+
+/// An issue summarized as "Summary", with the type unless it is empty.
+pub fn issue(key: &str, issue_type: &str, labels: &[&str]) -> Issue {
+    Issue {
+        issue_type: Some(issue_type.to_string()).filter(|t| !t.is_empty()),
+        labels: strings(labels),
+        ..Issue::new(key, "Summary")
+    }
+}
@@ -0,0 +1,77 @@
+use jira_git_helper::create::{build_fields, field_value, missing_fields, selected_value, NewIssue};
+use jira_git_helper::jira::{FieldMeta, IssueTypeMeta};
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn bug_type() -> IssueTypeMeta {
+    serde_json::from_value(json!({ "id": "1", "name": "Bug", "subtask": false })).unwrap()
+}
+
+fn create_screen() -> BTreeMap<String, FieldMeta> {
+    serde_json::from_value(json!({
+        "summary": { "name": "Summary", "required": true, "schema": { "type": "string" } },
+        "labels": { "name": "Labels", "required": false, "schema": { "type": "array", "items": "string" } },
+        "components": {
+            "name": "Component/s",
+            "required": true,
+            "schema": { "type": "array", "items": "component" },
+            "allowedValues": [{ "id": "10", "name": "Backend" }, { "id": "11", "name": "Frontend" }]
+        },
+        "priority": {
+            "name": "Priority",
+            "required": false,
+            "schema": { "type": "priority" },
+            "allowedValues": [{ "id": "2", "name": "High" }, { "id": "3", "name": "Medium" }]
+        },
+        "reporter": { "name": "Reporter", "required": true, "hasDefaultValue": true, "schema": { "type": "user" } },
+        "customfield_100": { "name": "Story Points", "required": true, "schema": { "type": "number" } }
+    }))
+    .unwrap()
+}
+
+fn new_issue() -> NewIssue {
+    NewIssue {
+        project: "PROJ".to_string(),
+        summary: "Login page crashes".to_string(),
+        labels: vec!["ui".to_string()],
+        priority: Some("high".to_string()),
+        ..NewIssue::default()
+    }
+}
+
+#[test]
+fn test_build_fields_validates_against_screen() {
+    let screen = create_screen();
+    let fields = build_fields(&new_issue(), &bug_type(), &screen, Some(json!("Steps"))).unwrap();
+    assert_eq!(fields["project"], json!({ "key": "PROJ" }));
+    assert_eq!(fields["issuetype"], json!({ "id": "1" }));
+    assert_eq!(fields["priority"], json!({ "id": "2" }));
+    assert_eq!(fields["labels"], json!(["ui"]));
+    assert_eq!(fields["description"], json!("Steps"));
+
+    let missing: Vec<&str> = missing_fields(&screen, &fields).into_iter().map(|(id, _)| id).collect();
+    assert_eq!(missing, vec!["components", "customfield_100"]);
+
+    let unknown_priority = NewIssue { priority: Some("Urgent".to_string()), ..new_issue() };
+    assert!(build_fields(&unknown_priority, &bug_type(), &screen, None).is_err());
+    let spaced_label = NewIssue { labels: vec!["two words".to_string()], ..new_issue() };
+    assert!(build_fields(&spaced_label, &bug_type(), &screen, None).is_err());
+    let subtask: IssueTypeMeta = serde_json::from_value(json!({ "id": "5", "name": "Sub-task", "subtask": true })).unwrap();
+    assert!(build_fields(&new_issue(), &subtask, &screen, None).is_err());
+}
+
+#[test]
+fn test_field_value_follows_schema() {
+    let screen = create_screen();
+    assert_eq!(field_value(&screen["components"], "frontend, Backend").unwrap(), json!([{ "id": "11" }, { "id": "10" }]));
+    assert_eq!(field_value(&screen["customfield_100"], "3").unwrap(), json!(3.0));
+    assert!(field_value(&screen["customfield_100"], "three").is_err());
+    assert_eq!(field_value(&screen["labels"], "a,b").unwrap(), json!(["a", "b"]));
+}
+
+#[test]
+fn test_selected_value_uses_the_chosen_entry() {
+    let screen = create_screen();
+    assert_eq!(selected_value(&screen["components"], 1), json!([{ "id": "11" }]));
+    assert_eq!(selected_value(&screen["priority"], 0), json!({ "id": "2" }));
+}
@@ -0,0 +1,55 @@
+use jira_git_helper::git::CommitInfo;
+use jira_git_helper::history::{collect_refs, distinct_keys, Checkpoint, SyncMode, SyncSummary};
+use jira_git_helper::issue_key::{KeyExtractionSettings, KeyExtractor};
+use tempfile::tempdir;
+
+fn commit(hash: &str, message: &str) -> CommitInfo {
+    CommitInfo {
+        hash: hash.to_string(),
+        short_hash: hash[..3].to_string(),
+        message: message.to_string(),
+        ..CommitInfo::default()
+    }
+}
+
+#[test]
+fn test_collect_refs_and_distinct_keys() {
+    let extractor = KeyExtractor::new(&KeyExtractionSettings::default()).unwrap();
+    let commits = vec![
+        commit("aaa1", "[PROJ-1] Add login"),
+        commit("bbb2", "Bump version to 1.2"),
+        commit("ccc3", "[PROJ-2] Fix crash\n\nAlso refs PROJ-1"),
+    ];
+
+    let refs = collect_refs(commits, &extractor);
+    assert_eq!(refs.len(), 2);
+    assert_eq!(refs[1].keys, vec!["PROJ-2", "PROJ-1"]);
+    assert_eq!(distinct_keys(&refs), vec!["PROJ-1", "PROJ-2"]);
+}
+
+#[test]
+fn test_checkpoint_resumes_per_mode() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("state/sync-history");
+
+    let mut checkpoint = Checkpoint::load(&path, SyncMode::Link).unwrap();
+    assert!(checkpoint.is_empty());
+    checkpoint.record(&path, "aaa1").unwrap();
+    checkpoint.record(&path, "ccc3").unwrap();
+
+    let reloaded = Checkpoint::load(&path, SyncMode::Link).unwrap();
+    assert!(reloaded.contains("aaa1") && reloaded.contains("ccc3"));
+    assert!(!Checkpoint::load(&path, SyncMode::Comment).unwrap().contains("aaa1"));
+}
+
+#[test]
+fn test_summary_lists_problem_keys() {
+    let mut summary = SyncSummary { dry_run: true, commits_scanned: 3, commits_with_keys: 2, created: 2, ..SyncSummary::default() };
+    summary.unknown_keys.insert("PROJ-9".to_string(), vec!["ccc".to_string()]);
+    summary.inaccessible_keys.insert("SEC-1".to_string(), "403 Forbidden".to_string());
+
+    let report = summary.to_string();
+    assert!(report.contains("Would create:           2"));
+    assert!(report.contains("Unknown keys:\n  PROJ-9 (ccc)"));
+    assert!(report.contains("Inaccessible keys:\n  SEC-1: 403 Forbidden"));
+}
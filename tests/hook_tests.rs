@@ -0,0 +1,64 @@
+use jira_git_helper::hook::{is_closed, strip_comments, validate_message, CommitMsgSettings, KeyPosition};
+use jira_git_helper::issue::{IssueStatus, StatusCategory};
+use jira_git_helper::AppError;
+
+fn settings(position: KeyPosition) -> CommitMsgSettings {
+    CommitMsgSettings {
+        projects: vec!["PROJ".to_string()],
+        position,
+        ..CommitMsgSettings::default()
+    }
+}
+
+#[test]
+fn test_strip_comments() {
+    let message = "[PROJ-1] Fix login\n\nBody\n# Please enter the commit message\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+    assert_eq!(strip_comments(message), "[PROJ-1] Fix login\n\nBody");
+}
+
+#[test]
+fn test_validate_message_positions() {
+    let body_only = "Fix login\n\nRefs PROJ-12";
+    assert_eq!(validate_message(body_only, &settings(KeyPosition::Anywhere)).unwrap(), vec!["PROJ-12"]);
+    assert!(matches!(
+        validate_message(body_only, &settings(KeyPosition::Subject)),
+        Err(AppError::CommitRejected(_))
+    ));
+
+    let prefixed = "[PROJ-12][PROJ-15] Fix login";
+    assert_eq!(
+        validate_message(prefixed, &settings(KeyPosition::Prefix)).unwrap(),
+        vec!["PROJ-12", "PROJ-15"]
+    );
+    assert_eq!(
+        validate_message("PROJ-12: Fix PROJ-13 login", &settings(KeyPosition::Prefix)).unwrap(),
+        vec!["PROJ-12"]
+    );
+    assert!(validate_message("Fix login for PROJ-12", &settings(KeyPosition::Prefix)).is_err());
+}
+
+#[test]
+fn test_validate_message_requires_exact_project() {
+    let result = validate_message("[PROJX-3] Fix login", &settings(KeyPosition::Anywhere));
+    assert!(matches!(result, Err(AppError::CommitRejected(_))));
+}
+
+#[test]
+fn test_validate_message_exemptions() {
+    let settings = settings(KeyPosition::Prefix);
+    assert!(validate_message("Merge branch 'main' into feature", &settings).unwrap().is_empty());
+    assert!(validate_message("fixup! [PROJ-1] Fix login", &settings).unwrap().is_empty());
+}
+
+#[test]
+fn test_is_closed() {
+    let status = IssueStatus {
+        name: "Done".to_string(),
+        category: Some(StatusCategory { key: "done".to_string(), name: "Done".to_string() }),
+    };
+    let mut settings = CommitMsgSettings::default();
+    assert!(is_closed(&status, &settings));
+
+    settings.closed_statuses = vec!["Closed".to_string()];
+    assert!(!is_closed(&status, &settings));
+}
@@ -0,0 +1,88 @@
+use jira_git_helper::issue_key::{parse_issue_reference, KeyExtractionSettings, KeyExtractor, KeyRule};
+
+fn extractor(projects: &[&str], default_project: Option<&str>) -> KeyExtractor {
+    KeyExtractor::new(&KeyExtractionSettings {
+        projects: projects.iter().map(|p| p.to_string()).collect(),
+        default_project: default_project.map(str::to_string),
+        ..KeyExtractionSettings::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_extract_all_jira_ids() {
+    assert_eq!(
+        extractor(&["PROJ"], None).extract("feature/PROJ-12-PROJ-15-combined"),
+        vec!["PROJ-12", "PROJ-15"]
+    );
+}
+
+#[test]
+fn test_extract_jira_ids_deduplicates() {
+    let extractor = extractor(&["PROJ"], None);
+    assert_eq!(extractor.extract("proj-7/PROJ-7-retry"), vec!["PROJ-7"]);
+    assert!(extractor.extract("main").is_empty());
+}
+
+#[test]
+fn test_extract_keys_with_digits_and_underscores() {
+    let extractor = extractor(&[], None);
+    assert_eq!(extractor.extract("feature/AB2-17-login"), vec!["AB2-17"]);
+    assert_eq!(extractor.extract("bugfix/MY_PROJ-4"), vec!["MY_PROJ-4"]);
+}
+
+#[test]
+fn test_lowercase_keys_need_configured_projects() {
+    let any_project = extractor(&[], None);
+    assert!(any_project.extract("proj_123").is_empty());
+    assert!(any_project.extract("fix-login-123").is_empty());
+    assert!(any_project.extract_from_text("Support utf_8 and utf-16").is_empty());
+
+    let configured = extractor(&["PROJ"], None);
+    assert_eq!(configured.extract("proj_123"), vec!["PROJ-123"]);
+    assert_eq!(configured.extract("feature/Proj-7-login"), vec!["PROJ-7"]);
+}
+
+#[test]
+fn test_extract_bare_number_uses_default_project() {
+    let extractor = extractor(&["PROJ"], Some("proj"));
+    assert_eq!(extractor.extract("123-fix-login"), vec!["PROJ-123"]);
+    assert_eq!(extractor.extract("hotfix/45_crash"), vec!["PROJ-45"]);
+}
+
+#[test]
+fn test_project_matching_is_exact() {
+    let extractor = extractor(&["PRO"], None);
+    assert!(extractor.extract("feature/PROJ-1").is_empty());
+    assert_eq!(extractor.extract("feature/PRO-1"), vec!["PRO-1"]);
+}
+
+#[test]
+fn test_custom_rule_requires_number_group() {
+    let settings = KeyExtractionSettings {
+        rules: vec![KeyRule { name: "broken".to_string(), pattern: r"(?P<project>[A-Z]+)".to_string() }],
+        ..KeyExtractionSettings::default()
+    };
+    assert!(KeyExtractor::new(&settings).is_err());
+}
+
+#[test]
+fn test_extract_from_text_ignores_bare_numbers() {
+    let extractor = extractor(&["PROJ"], Some("PROJ"));
+    assert!(extractor.extract_from_text("123 files changed").is_empty());
+    assert_eq!(extractor.extract_from_text("[PROJ-9] Fix login\n\nSee PROJ-10"), vec!["PROJ-9", "PROJ-10"]);
+}
+
+#[test]
+fn test_parse_issue_reference() {
+    assert_eq!(parse_issue_reference("proj-12").as_deref(), Some("PROJ-12"));
+    assert_eq!(
+        parse_issue_reference("https://jira.example.com/browse/PROJ-12?focusedCommentId=1").as_deref(),
+        Some("PROJ-12")
+    );
+    assert_eq!(
+        parse_issue_reference("https://jira.example.com/secure/RapidBoard.jspa?rapidView=3&selectedIssue=PROJ-7").as_deref(),
+        Some("PROJ-7")
+    );
+    assert_eq!(parse_issue_reference("not an issue"), None);
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use jira_git_helper::git::CommitInfo;
+use jira_git_helper::issue::User;
+use jira_git_helper::issue_log::{check_git_args, format_lines, CachedIssue, IssueCache, LogFilter, LogLine};
+use std::collections::HashMap;
+use tempfile::tempdir;
+
+use common::{commit, strings};
+
+fn cached(summary: &str, status: &str, assignee: Option<&str>, fetched_at: u64) -> CachedIssue {
+    CachedIssue {
+        summary: summary.to_string(),
+        status: status.to_string(),
+        assignee: assignee.map(|name| User {
+            display_name: name.to_string(),
+            name: Some(name.to_lowercase().replace(' ', ".")),
+            ..User::default()
+        }),
+        fetched_at,
+    }
+}
+
+fn line<'a>(graph: &str, commit: Option<&'a CommitInfo>, keys: &'a [String], hidden: bool) -> LogLine<'a> {
+    LogLine {
+        graph: graph.to_string(),
+        commit,
+        keys,
+        hidden,
+    }
+}
+
+#[test]
+fn test_cache_ttl_and_persistence() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("cache/issues.json");
+    assert!(IssueCache::load(&path).get("PROJ-1", 100, 300).is_none());
+
+    let mut cache = IssueCache::default();
+    cache.insert("PROJ-1", cached("Login", "Done", None, 100));
+    cache.insert("PROJ-2", cached("Export", "To Do", None, 500));
+    cache.save(&path).unwrap();
+
+    let mut cache = IssueCache::load(&path);
+    assert_eq!(cache.get("PROJ-1", 399, 300).map(|i| i.summary.as_str()), Some("Login"));
+    assert!(cache.get("PROJ-1", 400, 300).is_none());
+
+    cache.prune(400, 300);
+    assert!(cache.get("PROJ-1", 100, 300).is_none());
+    assert!(cache.get("PROJ-2", 400, 300).is_some());
+}
+
+#[test]
+fn test_filter_matches() {
+    let issues: HashMap<String, CachedIssue> = [
+        ("PROJ-1".to_string(), cached("Login", "Done", Some("Ada Lovelace"), 0)),
+        ("PROJ-2".to_string(), cached("Export", "In Progress", None, 0)),
+    ]
+    .into_iter()
+    .collect();
+
+    assert!(LogFilter::default().matches(&[], &issues));
+
+    let by_issue = LogFilter {
+        issue: Some("proj-2".to_string()),
+        ..LogFilter::default()
+    };
+    assert!(by_issue.matches(&strings(&["PROJ-1", "PROJ-2"]), &issues));
+    assert!(!by_issue.matches(&strings(&["PROJ-1"]), &issues));
+    assert!(!by_issue.matches(&[], &issues));
+
+    let by_status_and_assignee = LogFilter {
+        status: Some("done".to_string()),
+        assignee: Some("lovelace".to_string()),
+        ..LogFilter::default()
+    };
+    assert!(by_status_and_assignee.matches(&strings(&["PROJ-2", "PROJ-1"]), &issues));
+    assert!(!by_status_and_assignee.matches(&strings(&["PROJ-2", "PROJ-3"]), &issues));
+
+    let by_username = LogFilter {
+        assignee: Some("ada.lovelace".to_string()),
+        ..LogFilter::default()
+    };
+    assert!(by_username.matches(&strings(&["PROJ-1"]), &issues));
+}
+
+#[test]
+fn test_format_lines_with_graph() {
+    let issues: HashMap<String, CachedIssue> = [
+        ("PROJ-1".to_string(), cached("Add login", "Done", None, 0)),
+        ("PROJ-12".to_string(), cached("Export reports", "In Progress", None, 0)),
+    ]
+    .into_iter()
+    .collect();
+    let merge = commit("f4ab5f4", "Merge branch 'feature'");
+    let feature = commit("e6a1c77", "[PROJ-12] Export reports");
+    let fix = commit("ceb94a8", "[PROJ-1] [PROJ-12] Fix login");
+    let typo = commit("5281aca", "Fix typo");
+    let (k0, k1, k2) = (strings(&[]), strings(&["PROJ-12"]), strings(&["PROJ-1", "PROJ-12"]));
+
+    let lines = vec![
+        line("*   ", Some(&merge), &k0, true),
+        line("|\\  ", None, &k0, false),
+        line("| * ", Some(&feature), &k1, false),
+        line("* | ", Some(&fix), &k2, false),
+        line("|/  ", None, &k0, false),
+        line("* ", Some(&typo), &k0, false),
+    ];
+
+    let expected = "\
+*   f4ab5f4
+|\\
+| * e6a1c77  PROJ-12    In Progress  Export reports
+* | ceb94a8  PROJ-1 +1  Done         Add login
+|/
+*   5281aca                          Fix typo";
+    assert_eq!(format_lines(&lines, &issues, 120), expected);
+}
+
+#[test]
+fn test_check_git_args() {
+    assert!(check_git_args(&strings(&["--oneline", "--format=%s", "main..HEAD", "--", "-p"])).is_ok());
+    assert!(check_git_args(&strings(&["-p"])).is_err());
+    assert!(check_git_args(&strings(&["--stat=80"])).is_err());
+    assert!(check_git_args(&strings(&["--graph"])).is_err());
+}
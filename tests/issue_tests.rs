@@ -0,0 +1,72 @@
+use jira_git_helper::issue::{Issue, RichText};
+use serde_json::json;
+
+#[test]
+fn test_deserialize_issue() {
+    let mut issue: Issue = serde_json::from_value(json!({
+        "key": "PROJ-12",
+        "fields": {
+            "summary": "Fix login",
+            "issuetype": { "name": "Bug" },
+            "status": { "name": "In Progress", "statusCategory": { "key": "indeterminate", "name": "In Progress" } },
+            "priority": { "name": "High" },
+            "assignee": { "displayName": "Ada", "name": "ada" },
+            "reporter": null,
+            "labels": ["ui"],
+            "components": [{ "name": "Web" }],
+            "fixVersions": [{ "name": "1.2.0" }],
+            "parent": { "key": "PROJ-10", "fields": { "summary": "Login epic" } },
+            "subtasks": [{ "key": "PROJ-13", "fields": { "summary": "Add test" } }],
+            "description": "h2. Steps",
+            "customfield_10008": "PROJ-1",
+            "customfield_10020": "PROJ-2"
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(issue.key, "PROJ-12");
+    assert_eq!(issue.issue_type.as_deref(), Some("Bug"));
+    assert_eq!(issue.status_name(), "In Progress");
+    assert_eq!(issue.assignee_name(), "Ada");
+    assert!(issue.reporter.is_none());
+    assert_eq!(issue.components, vec!["Web"]);
+    assert_eq!(issue.fix_versions, vec!["1.2.0"]);
+    assert_eq!(issue.parent.as_ref().unwrap().summary, "Login epic");
+    assert_eq!(issue.subtasks[0].key, "PROJ-13");
+    assert!(issue.epic.is_none());
+    issue.resolve_epic("customfield_10008");
+    assert_eq!(issue.epic.as_deref(), Some("PROJ-1"));
+    assert!(matches!(issue.description, Some(RichText::Wiki(_))));
+}
+
+#[test]
+fn test_deserialize_adf_description() {
+    let issue: Issue = serde_json::from_value(json!({
+        "key": "PROJ-1",
+        "fields": { "summary": "x", "description": { "type": "doc", "version": 1, "content": [] } }
+    }))
+    .unwrap();
+    assert!(matches!(issue.description, Some(RichText::Adf(_))));
+}
+
+#[test]
+fn test_serialized_issue_reads_back() {
+    let issue = Issue {
+        issue_type: Some("Bug".to_string()),
+        components: vec!["Web".to_string()],
+        fix_versions: vec!["1.2.0".to_string()],
+        custom_fields: [("customfield_10008".to_string(), json!("PROJ-1"))].into_iter().collect(),
+        ..Issue::new("PROJ-12", "Fix login")
+    };
+
+    let value = serde_json::to_value(&issue).unwrap();
+    assert_eq!(value["fields"]["issuetype"]["name"], "Bug");
+    assert_eq!(value["fields"]["fixVersions"][0]["name"], "1.2.0");
+
+    let mut read: Issue = serde_json::from_value(value).unwrap();
+    read.resolve_epic("customfield_10008");
+    assert_eq!(read.key, "PROJ-12");
+    assert_eq!(read.summary, "Fix login");
+    assert_eq!(read.components, vec!["Web"]);
+    assert_eq!(read.epic.as_deref(), Some("PROJ-1"));
+}
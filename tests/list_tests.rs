@@ -0,0 +1,63 @@
+use jira_git_helper::issue::Issue;
+use jira_git_helper::list::{build_jql, format_table, ListFilter};
+
+#[test]
+fn test_build_jql_presets() {
+    assert_eq!(
+        build_jql(&ListFilter::default()),
+        "assignee = currentUser() AND resolution = Unresolved ORDER BY updated DESC"
+    );
+
+    let filter = ListFilter {
+        mine: true,
+        sprint: true,
+        project: Some("PROJ".to_string()),
+        status: Some("In Progress".to_string()),
+        ..ListFilter::default()
+    };
+    assert_eq!(
+        build_jql(&filter),
+        "assignee = currentUser() AND sprint in openSprints() AND project = \"PROJ\" AND status = \"In Progress\" ORDER BY updated DESC"
+    );
+}
+
+#[test]
+fn test_build_jql_keeps_raw_order_by() {
+    let filter = ListFilter {
+        mine: true,
+        jql: Some("labels = ui order by priority DESC".to_string()),
+        ..ListFilter::default()
+    };
+    assert_eq!(build_jql(&filter), "(labels = ui) AND assignee = currentUser() order by priority DESC");
+
+    let quoted = ListFilter {
+        jql: Some("summary ~ \"sort order by date\"".to_string()),
+        ..ListFilter::default()
+    };
+    assert_eq!(build_jql(&quoted), "(summary ~ \"sort order by date\") ORDER BY updated DESC");
+
+    let border = ListFilter {
+        jql: Some("labels = border by-law".to_string()),
+        ..ListFilter::default()
+    };
+    assert_eq!(build_jql(&border), "(labels = border by-law) ORDER BY updated DESC");
+
+    let spaced = ListFilter {
+        jql: Some("labels = BORDER ORDER  BY created".to_string()),
+        ..ListFilter::default()
+    };
+    assert_eq!(build_jql(&spaced), "(labels = BORDER) ORDER  BY created");
+}
+
+#[test]
+fn test_format_table() {
+    let issues = vec![
+        Issue { issue_type: Some("Bug".to_string()), ..Issue::new("PROJ-1", "Fix login page crash on Safari") },
+        Issue::new("PROJ-22", "修复登录"),
+    ];
+    let table = format_table(&issues, 60);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "KEY      TYPE  STATUS  PRIORITY  ASSIGNEE  SUMMARY");
+    assert_eq!(lines[1], "PROJ-1   Bug                               Fix login page…");
+    assert_eq!(lines[2], format!("PROJ-22{}修复登录", " ".repeat(36)));
+}
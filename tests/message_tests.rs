@@ -0,0 +1,87 @@
+use jira_git_helper::issue::Issue;
+use jira_git_helper::message::{
+    build_trailers, display_width, fill_template, render, render_with_limit, truncate_at_word, ExtraTrailer, SubjectSettings,
+    TrailerSettings, DEFAULT_TEMPLATE,
+};
+
+fn issue(key: &str, summary: &str) -> Issue {
+    Issue::new(key, summary)
+}
+
+#[test]
+fn test_render_default_template() {
+    let issues = vec![issue("PROJ-12", "Fix login")];
+    assert_eq!(render(DEFAULT_TEMPLATE, &issues, "handle empty password"), "[PROJ-12] Fix login handle empty password");
+    assert_eq!(render(DEFAULT_TEMPLATE, &issues, ""), "[PROJ-12] Fix login");
+}
+
+#[test]
+fn test_render_multiple_keys() {
+    let issues = vec![issue("PROJ-12", "Fix login"), issue("PROJ-15", "Fix logout")];
+    assert_eq!(render(DEFAULT_TEMPLATE, &issues, ""), "[PROJ-12][PROJ-15] Fix login / Fix logout");
+    assert_eq!(render("{key}: {message}", &issues, "cleanup"), "PROJ-12: cleanup");
+}
+
+#[test]
+fn test_build_trailers() {
+    let settings = TrailerSettings {
+        enabled: true,
+        extra: vec![ExtraTrailer { name: "Jira-URL".to_string(), value: "{url}".to_string() }],
+        ..TrailerSettings::default()
+    };
+    let issues = vec![issue("PROJ-12", "Fix login"), issue("PROJ-15", "Fix logout")];
+    assert_eq!(
+        build_trailers(&settings, &issues, "https://jira.example.com/"),
+        vec![
+            ("Jira".to_string(), "PROJ-12".to_string()),
+            ("Jira".to_string(), "PROJ-15".to_string()),
+            ("Jira-URL".to_string(), "https://jira.example.com/browse/PROJ-12".to_string()),
+            ("Jira-URL".to_string(), "https://jira.example.com/browse/PROJ-15".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_truncate_at_word() {
+    assert_eq!(truncate_at_word("Fix login page crash on Safari", 20, "…"), "Fix login page…");
+    assert_eq!(truncate_at_word("Short", 20, "…"), "Short");
+    // 每个汉字占两列
+    assert_eq!(display_width("修复登录页面崩溃"), 16);
+    assert_eq!(truncate_at_word("修复登录页面崩溃", 9, "…"), "修复登录…");
+}
+
+#[test]
+fn test_render_with_limit_moves_summary_to_body() {
+    let settings = SubjectSettings { max_length: Some(30), ..SubjectSettings::default() };
+    let issues = vec![issue("PROJ-12", "Fix login page crash when the password is empty")];
+
+    let message = render_with_limit(DEFAULT_TEMPLATE, &issues, "check length first", &settings);
+    let mut lines = message.lines();
+    let subject = lines.next().unwrap();
+    assert_eq!(subject, "[PROJ-12] Fix login page…");
+    assert!(display_width(subject) <= 30);
+    assert_eq!(
+        lines.collect::<Vec<_>>(),
+        vec!["", "Fix login page crash when the password is empty", "", "check length first"]
+    );
+
+    let short = vec![issue("PROJ-12", "Fix login")];
+    assert_eq!(render_with_limit(DEFAULT_TEMPLATE, &short, "", &settings), "[PROJ-12] Fix login");
+}
+
+#[test]
+fn test_render_issue_fields() {
+    let issues = vec![Issue {
+        issue_type: Some("Bug".to_string()),
+        labels: vec!["ui".to_string(), "login".to_string()],
+        ..issue("PROJ-12", "Fix login")
+    }];
+    assert_eq!(render("{type}: {summary} ({labels})", &issues, ""), "Bug: Fix login (ui, login)");
+}
+
+#[test]
+fn test_render_does_not_expand_placeholders_in_values() {
+    let issues = vec![issue("PROJ-12", "Render {key} literally")];
+    assert_eq!(render(DEFAULT_TEMPLATE, &issues, "keep {summary}"), "[PROJ-12] Render {key} literally keep {summary}");
+    assert_eq!(fill_template("{a}{b} {c} {", &[("a", "{b}"), ("b", "x")]), "{b}x {c} {");
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use jira_git_helper::next_version::{latest_version, suggest, Bump, BumpRule, NextVersionSettings};
+use semver::Version;
+use serde_json::json;
+
+use common::issue;
+
+#[test]
+fn test_latest_version() {
+    let tags: Vec<String> = ["v1.2.0", "v1.10.0", "v1.9.3", "release-2", "v2.0.0-rc.1", "2.5.0"]
+        .iter()
+        .map(|t| t.to_string())
+        .collect();
+    let (tag, version) = latest_version(&tags, "v").unwrap();
+    assert_eq!(tag, "v1.10.0");
+    assert_eq!(version, Version::new(1, 10, 0));
+    assert!(latest_version(&tags, "app-").is_none());
+}
+
+#[test]
+fn test_bump_apply() {
+    let version = Version::parse("1.4.2").unwrap();
+    assert_eq!(Bump::Major.apply(&version), Version::new(2, 0, 0));
+    assert_eq!(Bump::Minor.apply(&version), Version::new(1, 5, 0));
+    assert_eq!(Bump::Patch.apply(&version), Version::new(1, 4, 3));
+}
+
+#[test]
+fn test_suggest_with_default_rules() {
+    let settings = NextVersionSettings::default();
+    let current = Version::new(1, 4, 2);
+
+    let patch = suggest(&current, &[issue("PROJ-1", "Bug", &[])], 2, &settings);
+    assert_eq!(patch.bump, Bump::Patch);
+    assert_eq!(patch.reasons.len(), 2);
+
+    let issues = vec![issue("PROJ-1", "Bug", &[]), issue("PROJ-2", "Story", &[]), issue("PROJ-3", "Task", &["Breaking"])];
+    let major = suggest(&current, &issues, 0, &settings);
+    assert_eq!(major.next, Version::new(2, 0, 0));
+    assert_eq!(major.reasons[0].subject, "PROJ-3 Summary");
+    assert_eq!(major.reasons[0].why, "label Breaking");
+    assert_eq!(major.reasons[1].why, "type Story");
+}
+
+#[test]
+fn test_suggest_with_field_rule() {
+    let settings = NextVersionSettings {
+        rules: vec![BumpRule {
+            bump: Bump::Major,
+            issue_types: Vec::new(),
+            labels: Vec::new(),
+            field: Some("customfield_10100".to_string()),
+            values: vec!["Yes".to_string()],
+        }],
+        ..NextVersionSettings::default()
+    };
+    assert_eq!(settings.fields(), vec!["customfield_10100"]);
+
+    let mut breaking = issue("PROJ-1", "Task", &[]);
+    breaking.custom_fields.insert("customfield_10100".to_string(), json!({ "value": "Yes" }));
+    let mut not_breaking = issue("PROJ-2", "Task", &[]);
+    not_breaking.custom_fields.insert("customfield_10100".to_string(), json!({ "value": "No" }));
+
+    let suggestion = suggest(&Version::new(0, 3, 0), &[not_breaking, breaking], 0, &settings);
+    assert_eq!(suggestion.bump, Bump::Major);
+    assert_eq!(suggestion.reasons[0].why, "customfield_10100 = Yes");
+    assert_eq!(suggestion.reasons[1].why, "default");
+}
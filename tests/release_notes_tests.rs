@@ -0,0 +1,99 @@
+mod common;
+
+use jira_git_helper::git::CommitInfo;
+use jira_git_helper::issue::Issue;
+use jira_git_helper::list::keys_jql;
+use jira_git_helper::release_notes::{GroupBy, NotesFormat, NotesTemplate, ReleaseNotes};
+
+use common::{issue, strings};
+
+const JIRA_URL: &str = "https://jira.example.com";
+
+fn component_issue(key: &str, summary: &str, issue_type: &str, components: &[&str]) -> Issue {
+    Issue {
+        summary: summary.to_string(),
+        components: strings(components),
+        ..issue(key, issue_type, &[])
+    }
+}
+
+fn sample_notes(group_by: GroupBy) -> ReleaseNotes {
+    let issues = vec![
+        component_issue("PROJ-10", "Add <b>login</b>", "Story", &["Web", "API"]),
+        component_issue("PROJ-9", "Fix crash", "Bug", &["API"]),
+        component_issue("PROJ-11", "Tidy up", "Task", &[]),
+    ];
+    let unkeyed = vec![CommitInfo {
+        hash: "abc1234def".to_string(),
+        short_hash: "abc1234".to_string(),
+        subject: "Bump version".to_string(),
+        ..CommitInfo::default()
+    }];
+    ReleaseNotes::build("Release notes v1.0..v1.1", &issues, &unkeyed, group_by, JIRA_URL)
+}
+
+#[test]
+fn test_keys_jql() {
+    let keys = vec!["PROJ-1".to_string(), "OPS-2".to_string()];
+    assert_eq!(keys_jql(&keys), "key in (PROJ-1, OPS-2) ORDER BY key ASC");
+}
+
+#[test]
+fn test_groups_by_component_with_ungrouped_last() {
+    let notes = sample_notes(GroupBy::Component);
+    let names: Vec<&str> = notes.groups.iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(names, vec!["API", "Web", "Other"]);
+
+    let api: Vec<&str> = notes.groups[0].issues.iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(api, vec!["PROJ-9", "PROJ-10"]);
+}
+
+#[test]
+fn test_render_markdown() {
+    let notes = sample_notes(GroupBy::Type);
+    let expected = "\
+# Release notes v1.0..v1.1
+
+## Bug
+- [PROJ-9](https://jira.example.com/browse/PROJ-9) Fix crash
+
+## Story
+- [PROJ-10](https://jira.example.com/browse/PROJ-10) Add <b>login</b>
+
+## Task
+- [PROJ-11](https://jira.example.com/browse/PROJ-11) Tidy up
+
+## Other changes
+- abc1234 Bump version
+";
+    assert_eq!(notes.render(NotesFormat::Markdown, &NotesTemplate::default()), expected);
+}
+
+#[test]
+fn test_render_html_escapes() {
+    let html = sample_notes(GroupBy::Type).render(NotesFormat::Html, &NotesTemplate::default());
+    assert!(html.contains("<li><a href=\"https://jira.example.com/browse/PROJ-10\">PROJ-10</a> Add &lt;b&gt;login&lt;/b&gt;</li>"));
+    assert!(html.contains("<h2>Other changes</h2>"));
+}
+
+#[test]
+fn test_render_custom_template() {
+    let template = NotesTemplate {
+        header: "{title}\n".to_string(),
+        group: "[{group}: {count}]\n".to_string(),
+        item: "{key} ({status}) {summary}".to_string(),
+        commits_heading: "[misc]\n".to_string(),
+        commit: "{short_hash}".to_string(),
+    };
+    let rendered = sample_notes(GroupBy::Type).render(NotesFormat::Template, &template);
+    assert!(rendered.starts_with("Release notes v1.0..v1.1\n[Bug: 1]\nPROJ-9 () Fix crash\n"));
+    assert!(rendered.ends_with("[misc]\nabc1234\n"));
+}
+
+#[test]
+fn test_render_json() {
+    let json = sample_notes(GroupBy::Type).render(NotesFormat::Json, &NotesTemplate::default());
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["groups"][0]["issues"][0]["key"], "PROJ-9");
+    assert_eq!(value["commits_without_issues"][0]["subject"], "Bump version");
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use jira_git_helper::issue::Issue;
+use jira_git_helper::release::{plan, IssueAction, ReleaseReport};
+
+use common::{issue, strings};
+
+fn tagged(key: &str, fix_versions: &[&str]) -> Issue {
+    Issue { fix_versions: strings(fix_versions), ..issue(key, "", &[]) }
+}
+
+#[test]
+fn test_plan() {
+    let issues = vec![
+        tagged("PROJ-1", &[]),
+        tagged("PROJ-2", &["1.2.0"]),
+        tagged("PROJ-3", &["1.1.0"]),
+        tagged("OPS-4", &[]),
+    ];
+
+    let actions: Vec<IssueAction> = plan(&issues, "proj", "1.2.0").into_iter().map(|(_, a)| a).collect();
+    assert_eq!(
+        actions,
+        vec![
+            IssueAction::AddVersion,
+            IssueAction::AlreadyTagged,
+            IssueAction::OtherVersion(vec!["1.1.0".to_string()]),
+            IssueAction::OtherProject,
+        ]
+    );
+}
+
+#[test]
+fn test_report() {
+    let mut report = ReleaseReport {
+        version: "1.2.0".to_string(),
+        dry_run: true,
+        created: true,
+        released: true,
+        ..Default::default()
+    };
+    for (key, action) in plan(&[tagged("PROJ-1", &[]), tagged("PROJ-3", &["1.1.0"])], "PROJ", "1.2.0") {
+        report.record(key, action);
+    }
+
+    assert_eq!(
+        report.to_string(),
+        "Would create version 1.2.0\n\
+         Would add 1.2.0 to 1 issue(s): PROJ-1\n\
+         Skipped, already on another version:\n  PROJ-3 (1.1.0)\n\
+         Would mark 1.2.0 as released"
+    );
+}
@@ -0,0 +1,44 @@
+use jira_git_helper::git::CommitInfo;
+use jira_git_helper::remote::RemoteRepo;
+use jira_git_helper::remote_link::{branch_link, commit_link, RemoteLinkSettings};
+
+fn commit() -> CommitInfo {
+    CommitInfo {
+        hash: "abc123def456".to_string(),
+        short_hash: "abc123d".to_string(),
+        subject: "Fix login crash".to_string(),
+        author: "Li Lei".to_string(),
+        ..CommitInfo::default()
+    }
+}
+
+#[test]
+fn test_commit_link_has_stable_global_id() {
+    let repo = RemoteRepo::parse("git@gitea.example.com:team/app.git").unwrap();
+    let link = commit_link(&repo, &RemoteLinkSettings::default(), &commit()).unwrap();
+    assert_eq!(link.global_id, "git-commit=https://gitea.example.com/team/app@abc123def456");
+    assert_eq!(link.url, "https://gitea.example.com/team/app/commit/abc123def456");
+    assert_eq!(link.title, "abc123d Fix login crash");
+
+    let branch = branch_link(&repo, &RemoteLinkSettings::default(), "bugfix/PROJ-1").unwrap();
+    assert_eq!(branch.global_id, "git-branch=https://gitea.example.com/team/app@bugfix/PROJ-1");
+    assert_eq!(branch.url, "https://gitea.example.com/team/app/src/branch/bugfix/PROJ-1");
+}
+
+#[test]
+fn test_link_url_override() {
+    let repo = RemoteRepo::parse("ssh://git@git.internal:2222/team/app.git").unwrap();
+    assert!(commit_link(&repo, &RemoteLinkSettings::default(), &commit()).is_none());
+
+    let settings = RemoteLinkSettings {
+        commit_url: Some("https://code.internal/{path}/commits/{short_hash}".to_string()),
+        branch_url: Some("{repo_url}/branches/{branch}".to_string()),
+        ..RemoteLinkSettings::default()
+    };
+    assert_eq!(commit_link(&repo, &settings, &commit()).unwrap().url, "https://code.internal/team/app/commits/abc123d");
+    assert_eq!(branch_link(&repo, &settings, "main").unwrap().url, "https://git.internal/team/app/branches/main");
+    assert_eq!(
+        branch_link(&repo, &settings, "fix/{path}").unwrap().url,
+        "https://git.internal/team/app/branches/fix/{path}"
+    );
+}
@@ -0,0 +1,50 @@
+use jira_git_helper::remote::{Provider, RemoteRepo};
+
+#[test]
+fn test_parse_remote_urls() {
+    let expected = RemoteRepo {
+        scheme: "https".to_string(),
+        host: "github.com".to_string(),
+        path: "owner/repo".to_string(),
+    };
+    assert_eq!(RemoteRepo::parse("git@github.com:owner/repo.git"), Some(expected.clone()));
+    assert_eq!(RemoteRepo::parse("https://github.com/owner/repo.git"), Some(expected.clone()));
+    assert_eq!(RemoteRepo::parse("ssh://git@github.com:22/owner/repo"), Some(expected));
+    assert_eq!(RemoteRepo::parse("https://token@git.example.com:8443/group/sub/repo.git").unwrap().host, "git.example.com:8443");
+    assert_eq!(RemoteRepo::parse("/srv/git/repo.git"), None);
+}
+
+#[test]
+fn test_commit_urls() {
+    let hash = "abc123";
+    let url = |remote: &str, provider: Option<Provider>| RemoteRepo::parse(remote).unwrap().commit_url(provider, hash);
+
+    assert_eq!(url("git@github.com:owner/repo.git", None).unwrap(), "https://github.com/owner/repo/commit/abc123");
+    assert_eq!(url("git@gitlab.com:group/sub/repo.git", None).unwrap(), "https://gitlab.com/group/sub/repo/-/commit/abc123");
+    assert_eq!(url("git@bitbucket.org:team/repo.git", None).unwrap(), "https://bitbucket.org/team/repo/commits/abc123");
+    assert_eq!(url("https://codeberg.org/owner/repo", None).unwrap(), "https://codeberg.org/owner/repo/commit/abc123");
+    assert_eq!(
+        url("https://git.example.com/scm/proj/repo.git", Some(Provider::Bitbucket)).unwrap(),
+        "https://git.example.com/projects/PROJ/repos/repo/commits/abc123"
+    );
+    assert_eq!(url("git@git.example.com:owner/repo.git", None), None);
+    assert_eq!(
+        url("git@git.example.com:owner/repo.git", Some(Provider::GitLab)).unwrap(),
+        "https://git.example.com/owner/repo/-/commit/abc123"
+    );
+    assert_eq!(
+        url("http://git.example.com:8080/owner/repo.git", Some(Provider::Gitea)).unwrap(),
+        "http://git.example.com:8080/owner/repo/commit/abc123"
+    );
+}
+
+#[test]
+fn test_branch_urls() {
+    let url = |remote: &str| RemoteRepo::parse(remote).unwrap().branch_url(None, "feature/PROJ-1");
+    assert_eq!(url("git@github.com:owner/repo.git").unwrap(), "https://github.com/owner/repo/tree/feature/PROJ-1");
+    assert_eq!(url("git@gitlab.com:owner/repo.git").unwrap(), "https://gitlab.com/owner/repo/-/tree/feature/PROJ-1");
+    assert_eq!(
+        url("https://bitbucket.example.com/scm/proj/repo.git").unwrap(),
+        "https://bitbucket.example.com/projects/PROJ/repos/repo/browse?at=refs/heads/feature/PROJ-1"
+    );
+}
@@ -0,0 +1,49 @@
+use jira_git_helper::issue::RichText;
+use jira_git_helper::render::Renderer;
+use serde_json::json;
+
+fn plain() -> Renderer {
+    Renderer::new(false, 40)
+}
+
+#[test]
+fn test_wiki_markup() {
+    let markup = "h2. Steps\n# Open *login* page\n# Enter {{admin}}\n* see [docs|https://example.com/docs]\n{code:java}\nint x = 1;\n{code}\nbq. quoted";
+    assert_eq!(
+        plain().wiki(markup),
+        "Steps\n1. Open login page\n2. Enter admin\n• see docs (https://example.com/docs)\n  │ int x = 1;\n> quoted"
+    );
+}
+
+#[test]
+fn test_wiki_keeps_snake_case() {
+    assert_eq!(plain().wiki("set my_var_name to _true_"), "set my_var_name to true");
+}
+
+#[test]
+fn test_adf_document() {
+    let doc = json!({
+        "type": "doc",
+        "version": 1,
+        "content": [
+            { "type": "heading", "attrs": { "level": 2 }, "content": [{ "type": "text", "text": "Acceptance" }] },
+            { "type": "bulletList", "content": [
+                { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                    { "type": "text", "text": "Works in " },
+                    { "type": "text", "text": "Safari", "marks": [{ "type": "link", "attrs": { "href": "https://apple.com" } }] }
+                ]}]}
+            ]},
+            { "type": "codeBlock", "content": [{ "type": "text", "text": "npm test" }] }
+        ]
+    });
+    assert_eq!(plain().adf(&doc), "Acceptance\n• Works in Safari (https://apple.com)\n  │ npm test");
+}
+
+#[test]
+fn test_wrap_keeps_hanging_indent() {
+    let text = RichText::Wiki("* one two three four five six seven eight nine ten".to_string());
+    assert_eq!(
+        Renderer::new(false, 20).rich_text(&text),
+        "• one two three four\n  five six seven\n  eight nine ten"
+    );
+}
@@ -0,0 +1,51 @@
+use config::{Config, File, FileFormat};
+use jira_git_helper::app_config::get_section;
+use jira_git_helper::sanitize::{Sanitizer, SummarySettings, Transform};
+
+fn sanitizer(steps: Vec<Transform>) -> Sanitizer {
+    Sanitizer::new(&SummarySettings { steps }).unwrap()
+}
+
+#[test]
+fn test_strip_tags_and_normalize() {
+    let sanitizer = sanitizer(vec![
+        Transform::Strip { pattern: r"\[[^\]]*\]|【[^】]*】".to_string() },
+        Transform::RemoveEmoji,
+        Transform::NormalizePunctuation,
+        Transform::NormalizeWhitespace,
+        Transform::TrimTrailingPunctuation,
+    ]);
+    assert_eq!(sanitizer.apply("【紧急】[Backend]  Fix  login 🔥 crash."), "Fix login crash");
+    assert_eq!(sanitizer.apply("修复登录（Safari）问题。"), "修复登录(Safari)问题");
+}
+
+#[test]
+fn test_replace_rules() {
+    let sanitizer = sanitizer(vec![Transform::Replace {
+        pattern: r"(?i)\bui\b".to_string(),
+        replacement: "UI".to_string(),
+    }]);
+    assert_eq!(sanitizer.apply("Fix ui glitch"), "Fix UI glitch");
+}
+
+#[test]
+fn test_steps_from_config() {
+    let config = Config::builder()
+        .add_source(File::from_str(
+            r#"
+            [[summary.steps]]
+            type = "strip"
+            pattern = '^\[[^\]]*\]\s*'
+
+            [[summary.steps]]
+            type = "normalize_whitespace"
+            "#,
+            FileFormat::Toml,
+        ))
+        .build()
+        .unwrap();
+
+    let settings: SummarySettings = get_section(&config, "summary").unwrap();
+    let sanitizer = Sanitizer::new(&settings).unwrap();
+    assert_eq!(sanitizer.apply("[Backend] Fix   login"), "Fix login");
+}
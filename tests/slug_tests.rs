@@ -0,0 +1,28 @@
+use jira_git_helper::slug::{sanitize_ref_name, slugify};
+
+#[test]
+fn test_slugify_transliterates_chinese() {
+    assert_eq!(slugify("修复登录页面崩溃", 50), "xiu-fu-deng-lu-ye-mian-beng-kui");
+    assert_eq!(slugify("修复OAuth2登录", 50), "xiu-fu-oauth2-deng-lu");
+    assert_eq!(slugify("Café déjà vu", 50), "cafe-deja-vu");
+}
+
+#[test]
+fn test_slugify_limits_length() {
+    assert_eq!(slugify("Add OAuth2 login (Google)", 50), "add-oauth2-login-google");
+    assert_eq!(slugify("Add OAuth2 login for Google accounts", 19), "add-oauth2-login");
+    assert_eq!(slugify("Add OAuth2 login for Google accounts", 20), "add-oauth2-login-for");
+    assert_eq!(slugify("修复登录页面崩溃", 12), "xiu-fu-deng");
+    assert_eq!(slugify("Supercalifragilistic", 5), "super");
+}
+
+#[test]
+fn test_sanitize_ref_name() {
+    assert_eq!(sanitize_ref_name("feature/PROJ-1-fix"), "feature/PROJ-1-fix");
+    assert_eq!(sanitize_ref_name("feature/a..b~c^d:e"), "feature/a.b-c-d-e");
+    assert_eq!(sanitize_ref_name("feature//.hidden/x.lock"), "feature/hidden/x");
+    assert_eq!(sanitize_ref_name("wip@{1}."), "wip-1}");
+    assert_eq!(sanitize_ref_name("@"), "branch");
+    assert_eq!(sanitize_ref_name(".."), "branch");
+    assert_eq!(sanitize_ref_name("--fix"), "branch-fix");
+}
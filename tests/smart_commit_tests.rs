@@ -0,0 +1,36 @@
+use jira_git_helper::smart_commit::{parse, SmartCommand};
+
+#[test]
+fn test_parse_smart_commit_commands() {
+    let parsed = parse("Fix indent #time 1w 2d 4h 30m Total work logged #comment corrected indent issue #start-progress").unwrap();
+    assert_eq!(parsed.message, "Fix indent");
+    assert_eq!(
+        parsed.commands,
+        vec![
+            SmartCommand::Time { time_spent: "1w 2d 4h 30m".to_string(), comment: "Total work logged".to_string() },
+            SmartCommand::Comment("corrected indent issue".to_string()),
+            SmartCommand::Transition { name: "start progress".to_string(), comment: String::new() },
+        ]
+    );
+    assert_eq!(parsed.commands[2].label(), "#start-progress");
+}
+
+#[test]
+fn test_parse_ignores_non_commands() {
+    let parsed = parse("Fix issue#12 and #34 in C#").unwrap();
+    assert_eq!(parsed.message, "Fix issue#12 and #34 in C#");
+    assert!(parsed.commands.is_empty());
+
+    let parsed = parse("#resolve Fixed in this commit").unwrap();
+    assert_eq!(parsed.message, "");
+    assert_eq!(
+        parsed.commands,
+        vec![SmartCommand::Transition { name: "resolve".to_string(), comment: "Fixed in this commit".to_string() }]
+    );
+}
+
+#[test]
+fn test_parse_rejects_invalid_time() {
+    assert!(parse("Fix #time soon").is_err());
+    assert!(parse("Fix #time 2x").is_err());
+}
@@ -0,0 +1,56 @@
+use jira_git_helper::jira::Transition;
+use jira_git_helper::workflow::{allowed_value_json, find_command_transition, has_comment_field, required_fields, transition_label};
+use serde_json::json;
+
+fn resolve_transition() -> Transition {
+    serde_json::from_value(json!({
+        "id": "31",
+        "name": "Resolve Issue",
+        "to": { "name": "Resolved" },
+        "fields": {
+            "resolution": {
+                "name": "Resolution",
+                "required": true,
+                "allowedValues": [{ "id": "1", "name": "Fixed" }, { "id": "2", "name": "Won't Fix" }]
+            },
+            "assignee": { "name": "Assignee", "required": true, "hasDefaultValue": true },
+            "fixVersions": { "name": "Fix Version/s", "required": false },
+            "comment": { "name": "Comment", "required": false }
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_required_fields_skip_defaults_and_comment() {
+    let transition = resolve_transition();
+    let required: Vec<&str> = required_fields(&transition).into_iter().map(|(id, _)| id).collect();
+    assert_eq!(required, vec!["resolution"]);
+    assert!(has_comment_field(&transition));
+    assert_eq!(transition_label(&transition), "Resolve Issue → Resolved");
+}
+
+#[test]
+fn test_allowed_value_json_prefers_id() {
+    let transition = resolve_transition();
+    let resolution = &transition.fields["resolution"];
+    assert_eq!(allowed_value_json(&resolution.allowed_values[1]), json!({ "id": "2" }));
+
+    let by_value = serde_json::from_value(json!({ "value": "Yes" })).unwrap();
+    assert_eq!(allowed_value_json(&by_value), json!({ "value": "Yes" }));
+}
+
+#[test]
+fn test_find_command_transition() {
+    let transitions: Vec<Transition> = serde_json::from_value(json!([
+        { "id": "11", "name": "Start Progress", "to": { "name": "In Progress" } },
+        { "id": "21", "name": "Resolve Issue", "to": { "name": "Resolved" } },
+        { "id": "31", "name": "Reopen Issue", "to": { "name": "Reopened" } },
+        { "id": "41", "name": "Reject", "to": { "name": "Closed" } }
+    ]))
+    .unwrap();
+    assert_eq!(find_command_transition(&transitions, "start progress").unwrap().id, "11");
+    assert_eq!(find_command_transition(&transitions, "resolve").unwrap().id, "21");
+    assert_eq!(find_command_transition(&transitions, "closed").unwrap().id, "41");
+    assert!(find_command_transition(&transitions, "re").is_none());
+}
@@ -0,0 +1,16 @@
+use jira_git_helper::worklog::normalize_duration;
+
+#[test]
+fn test_normalize_duration() {
+    assert_eq!(normalize_duration("1h30m").unwrap(), "1h 30m");
+    assert_eq!(normalize_duration(" 2d 4H ").unwrap(), "2d 4h");
+    assert_eq!(normalize_duration("1.5h").unwrap(), "1.5h");
+    assert_eq!(normalize_duration("1w2d3h4m").unwrap(), "1w 2d 3h 4m");
+}
+
+#[test]
+fn test_normalize_duration_rejects_invalid_input() {
+    for input in ["", "90", "1x", "30m1h", "1h1h", "0h", "h", "1..5h"] {
+        assert!(normalize_duration(input).is_err(), "{} should be rejected", input);
+    }
+}